@@ -0,0 +1,45 @@
+//! Benchmark: G2P conversion time against word length.
+//!
+//! Converts accented Spanish words of doubling length and prints the time
+//! per letter. If rule matching were quadratic in the word length, as it
+//! was with byte offsets, that time would double from row to row; it
+//! should grow far more slowly.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo bench --bench g2p_scaling
+//! ```
+
+use parlador::G2PConverter;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Runs per word length; the fastest one is reported.
+const RUNS: usize = 20;
+
+fn main() {
+    let g2p = G2PConverter::spanish();
+
+    println!("{:>8}  {:>12}  {:>12}", "letters", "time", "per letter");
+    for repeats in [16, 32, 64, 128, 256] {
+        let word = "canción".repeat(repeats);
+        let letters = word.chars().count();
+
+        let fastest = (0..RUNS)
+            .map(|_| {
+                let start = Instant::now();
+                black_box(g2p.convert(black_box(&word)).expect("conversion failed"));
+                start.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::ZERO);
+
+        println!(
+            "{:>8}  {:>10.1}us  {:>10.1}ns",
+            letters,
+            fastest.as_secs_f64() * 1e6,
+            fastest.as_secs_f64() * 1e9 / letters as f64
+        );
+    }
+}
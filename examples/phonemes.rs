@@ -19,7 +19,7 @@ fn print_usage() {
     println!("Usage: phonemes [OPTIONS] [TEXT]");
     println!();
     println!("Options:");
-    println!("  --language, -l <LANG>   Language: en (English) or es (Spanish). Default: en");
+    println!("  --language, -l <LANG>   Language: en (English), es (Spanish) or fr (French). Default: en");
     println!("  --format, -f <FORMAT>   Phoneme format: ipa or ascii. Default: ipa");
     println!("  --help, -h              Show this help message");
     println!();
@@ -89,6 +89,9 @@ fn main() -> Result<(), SynthesizerError> {
             Language::Spanish => {
                 "Hola mundo. Esta es una prueba de generación de fonemas.".to_string()
             }
+            Language::French => {
+                "Bonjour le monde. Ceci est un test de génération de phonèmes.".to_string()
+            }
         };
     }
 
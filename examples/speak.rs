@@ -10,9 +10,10 @@
 //! cargo run --example speak -- "Custom text to speak"
 //! cargo run --example speak -- --language es "Texto en español"
 //! cargo run --example speak -- --rate 200 --pitch 20 "Fast and high pitch"
+//! cargo run --example speak --features playback -- --play "Hello out loud"
 //! ```
 
-use parlador::{Language, Synthesizer, SynthesizerError, VoiceConfig, VoiceVariant};
+use parlador::{Endianness, Language, Synthesizer, SynthesizerError, VoiceConfig, VoiceVariant};
 use std::env;
 use std::fs::File;
 use std::io::Write;
@@ -21,12 +22,15 @@ fn print_usage() {
     println!("Usage: speak [OPTIONS] [TEXT]");
     println!();
     println!("Options:");
-    println!("  --language, -l <LANG>   Language: en (English) or es (Spanish). Default: en");
+    println!("  --language, -l <LANG>   Language: en (English), es (Spanish) or fr (French). Default: en");
     println!("  --rate, -r <WPM>        Speech rate in words per minute. Default: 175");
     println!("  --pitch, -p <PITCH>     Pitch adjustment (-100 to 100). Default: 0");
     println!("  --volume, -v <VOLUME>   Volume (0-200). Default: 100");
-    println!("  --voice <VARIANT>       Voice variant: m1, m2, m3, f1, f2, f3. Default: default");
-    println!("  --output, -o <FILE>     Output file (raw PCM). If not specified, prints info only.");
+    println!("  --voice <VARIANT>       Voice variant: m1, m2, m3, f1, f2, f3, child. Default: default");
+    println!("  --output, -o <FILE>     Output file (WAV if it ends in .wav, otherwise raw PCM).");
+    println!("                          If not specified, prints info only.");
+    println!("  --play                  Play the audio (requires the `playback` feature)");
+    println!("  --whisper               Whisper instead of speaking aloud");
     println!("  --help, -h              Show this help message");
     println!();
     println!("Examples:");
@@ -34,7 +38,7 @@ fn print_usage() {
     println!("  speak --language es \"¡Hola, mundo!\"");
     println!("  speak --rate 200 --pitch 20 \"Fast speech\"");
     println!("  speak --voice f1 \"Female voice\"");
-    println!("  speak --output output.raw \"Hello world\"");
+    println!("  speak --output output.wav \"Hello world\"");
 }
 
 fn parse_variant(s: &str) -> Option<VoiceVariant> {
@@ -46,6 +50,7 @@ fn parse_variant(s: &str) -> Option<VoiceVariant> {
         "f1" | "female1" => Some(VoiceVariant::Female1),
         "f2" | "female2" => Some(VoiceVariant::Female2),
         "f3" | "female3" => Some(VoiceVariant::Female3),
+        "c" | "child" => Some(VoiceVariant::Child),
         _ => None,
     }
 }
@@ -59,6 +64,8 @@ fn main() -> Result<(), SynthesizerError> {
     let mut volume: u8 = 100;
     let mut variant = VoiceVariant::Default;
     let mut output_file: Option<String> = None;
+    let mut play = false;
+    let mut whisper = false;
     let mut text = String::new();
 
     let mut i = 1;
@@ -107,6 +114,8 @@ fn main() -> Result<(), SynthesizerError> {
                     output_file = Some(args[i].clone());
                 }
             }
+            "--play" => play = true,
+            "--whisper" => whisper = true,
             s if !s.starts_with('-') => {
                 if !text.is_empty() {
                     text.push(' ');
@@ -129,6 +138,9 @@ fn main() -> Result<(), SynthesizerError> {
             Language::Spanish => {
                 "¡Hola! Esta es una demostración del sintetizador de voz Parlador.".to_string()
             }
+            Language::French => {
+                "Bonjour ! Ceci est une démonstration du synthétiseur vocal Parlador.".to_string()
+            }
         };
     }
 
@@ -137,7 +149,8 @@ fn main() -> Result<(), SynthesizerError> {
         .with_variant(variant)
         .with_rate(rate)
         .with_pitch(pitch)
-        .with_volume(volume);
+        .with_volume(volume)
+        .with_whisper(whisper);
 
     println!("Parlador Speech Synthesizer");
     println!("===========================");
@@ -158,25 +171,36 @@ fn main() -> Result<(), SynthesizerError> {
     println!("Duration: {:.2} seconds", audio.duration_secs());
 
     // Save to file if requested
-    if let Some(filename) = output_file {
+    if let Some(filename) = output_file.as_ref().filter(|f| f.ends_with(".wav")) {
+        audio.save_wav(filename)?;
+        println!("\nAudio saved to: {}", filename);
+    } else if let Some(filename) = output_file {
         let mut file = File::create(&filename).map_err(|e| {
             SynthesizerError::SystemError(format!("Failed to create file: {}", e))
         })?;
 
-        for sample in &audio.samples {
-            file.write_all(&sample.to_le_bytes()).map_err(|e| {
-                SynthesizerError::SystemError(format!("Failed to write to file: {}", e))
-            })?;
-        }
+        file.write_all(&audio.to_bytes(Endianness::Little)).map_err(|e| {
+            SynthesizerError::SystemError(format!("Failed to write to file: {}", e))
+        })?;
 
         println!("\nAudio saved to: {}", filename);
-        println!("To convert to WAV, use:");
+        println!("To convert to WAV, use --output with a .wav file name, or:");
         println!(
             "  sox -r {} -b 16 -e signed -c 1 {} output.wav",
             audio.sample_rate, filename
         );
     }
 
+    if play {
+        #[cfg(feature = "playback")]
+        {
+            println!("\nPlaying...");
+            audio.play()?;
+        }
+        #[cfg(not(feature = "playback"))]
+        eprintln!("\nPlayback needs the `playback` feature: cargo run --example speak --features playback");
+    }
+
     println!("\nDone!");
     Ok(())
 }
@@ -3,13 +3,43 @@
 //! This module implements a Klatt-style formant synthesizer that generates
 //! speech audio from phoneme sequences with formant specifications.
 
-use crate::error::Result;
-use crate::phoneme::{FormantValues, Phoneme, PhonemeCategory, PhonemeInventory};
+use crate::error::{Result, SynthesizerError};
+use crate::phoneme::{
+    FormantValues, Phoneme, PhonemeCategory, PhonemeInventory, StressLevel, EMPHASIS_MARK, PARENTHETICAL_MARK,
+    QUESTION_MARK, QUOTE_MARK, STATEMENT_MARK,
+};
+use crate::voice::{Language, VoiceConfig};
+use std::collections::hash_map::RandomState;
 use std::f32::consts::PI;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Sample rate for audio generation (Hz).
+/// Default sample rate for audio generation (Hz).
 pub const SAMPLE_RATE: u32 = 22050;
 
+/// Lowest output sample rate a voice can be configured with (Hz).
+pub const MIN_SAMPLE_RATE: u32 = 8000;
+
+/// Highest output sample rate a voice can be configured with (Hz).
+pub const MAX_SAMPLE_RATE: u32 = 48000;
+
+/// Quiet audio kept on each side of the sound by `AudioOutput::trim_silence`
+/// (seconds), so soft onsets and releases are not clipped.
+const TRIM_PADDING_SECS: f64 = 0.005;
+
+/// Byte order for serializing 16-bit samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default)]
+pub enum Endianness {
+    /// Least significant byte first (WAV, most desktop targets).
+    #[default]
+    Little,
+    /// Most significant byte first (AIFF, network order).
+    Big,
+}
+
 /// Audio output from speech synthesis.
 #[derive(Debug, Clone)]
 pub struct AudioOutput {
@@ -31,6 +61,26 @@ impl AudioOutput {
         }
     }
 
+    /// Creates audio from floating-point samples in the range -1.0 to 1.0.
+    ///
+    /// Uses the full 16-bit range: positive samples scale by 32767 and
+    /// negative ones by 32768, with rounding, so -1.0 maps to -32768 and
+    /// 1.0 to 32767. Out-of-range samples are clamped. The synthesizer's own
+    /// output still goes through `FormantSynthesizer::to_pcm16`, which
+    /// truncates and scales by 32767 on both sides, so existing renderings
+    /// are unchanged.
+    pub fn from_f32(samples: &[f32], sample_rate: u32, channels: u16) -> Self {
+        let pcm = samples
+            .iter()
+            .map(|&s| {
+                let clamped = s.clamp(-1.0, 1.0);
+                let scale = if clamped < 0.0 { 32768.0 } else { 32767.0 };
+                (clamped * scale).round() as i16
+            })
+            .collect();
+        Self::new(pcm, sample_rate, channels)
+    }
+
     /// Returns the duration of the audio in seconds.
     pub fn duration_secs(&self) -> f64 {
         self.samples.len() as f64 / (self.sample_rate as f64 * self.channels as f64)
@@ -40,10 +90,378 @@ impl AudioOutput {
     pub fn is_empty(&self) -> bool {
         self.samples.is_empty()
     }
+
+    /// Serializes the samples as raw 16-bit PCM in the given byte order.
+    pub fn to_bytes(&self, endian: Endianness) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.samples.len() * 2);
+        for sample in &self.samples {
+            match endian {
+                Endianness::Little => bytes.extend_from_slice(&sample.to_le_bytes()),
+                Endianness::Big => bytes.extend_from_slice(&sample.to_be_bytes()),
+            }
+        }
+        bytes
+    }
+
+    /// Writes the audio as a 16-bit PCM WAV file (RIFF/WAVE).
+    ///
+    /// The header is built from `sample_rate`, `channels` and the sample
+    /// count, with samples interleaved as stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the audio is too long for a WAV file or
+    /// writing fails.
+    pub fn write_wav<W: Write>(&self, writer: &mut W) -> Result<()> {
+        const BYTES_PER_SAMPLE: u16 = 2;
+        let data_len = u32::try_from(self.samples.len() * BYTES_PER_SAMPLE as usize)
+            .ok()
+            .filter(|len| *len <= u32::MAX - 36)
+            .ok_or_else(|| SynthesizerError::AudioError("audio too long for a WAV file".to_string()))?;
+        // Chunks are padded to an even length; 16-bit data always is
+        let pad = data_len % 2;
+        let block_align = self.channels * BYTES_PER_SAMPLE;
+        let byte_rate = self.sample_rate * block_align as u32;
+
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(36 + data_len + pad).to_le_bytes());
+        header.extend_from_slice(b"WAVE");
+        header.extend_from_slice(b"fmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        header.extend_from_slice(&self.channels.to_le_bytes());
+        header.extend_from_slice(&self.sample_rate.to_le_bytes());
+        header.extend_from_slice(&byte_rate.to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&(BYTES_PER_SAMPLE * 8).to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&data_len.to_le_bytes());
+
+        let write_err = |e: std::io::Error| SynthesizerError::AudioError(format!("failed to write WAV: {}", e));
+        writer.write_all(&header).map_err(write_err)?;
+        writer.write_all(&self.to_bytes(Endianness::Little)).map_err(write_err)?;
+        if pad == 1 {
+            writer.write_all(&[0]).map_err(write_err)?;
+        }
+        writer.flush().map_err(write_err)
+    }
+
+    /// Saves the audio to a 16-bit PCM WAV file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the file cannot be created or written.
+    pub fn save_wav<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let file = std::fs::File::create(path).map_err(|e| {
+            SynthesizerError::AudioError(format!("failed to create {}: {}", path.display(), e))
+        })?;
+        self.write_wav(&mut std::io::BufWriter::new(file))
+    }
+
+    /// Root-mean-square difference between two outputs, in sample units.
+    ///
+    /// Returns 0.0 for identical audio. Useful for regression tests that
+    /// check output is unchanged, or changed by a measurable amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the outputs differ in length, sample rate or
+    /// channel count.
+    pub fn rms_difference(&self, other: &AudioOutput) -> Result<f32> {
+        self.check_comparable(other)?;
+        if self.samples.is_empty() {
+            return Ok(0.0);
+        }
+        let sum: f64 = self
+            .samples
+            .iter()
+            .zip(&other.samples)
+            .map(|(&a, &b)| (a as f64 - b as f64).powi(2))
+            .sum();
+        Ok((sum / self.samples.len() as f64).sqrt() as f32)
+    }
+
+    /// Largest absolute sample difference between two outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the outputs differ in length, sample rate or
+    /// channel count.
+    pub fn max_abs_difference(&self, other: &AudioOutput) -> Result<u16> {
+        self.check_comparable(other)?;
+        Ok(self
+            .samples
+            .iter()
+            .zip(&other.samples)
+            .map(|(&a, &b)| a.abs_diff(b))
+            .max()
+            .unwrap_or(0))
+    }
+
+    /// Appends `other` to the end of this audio.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the outputs differ in sample rate or channel
+    /// count.
+    pub fn append(&mut self, other: &AudioOutput) -> Result<()> {
+        self.check_same_format(other)?;
+        self.samples.extend_from_slice(&other.samples);
+        Ok(())
+    }
+
+    /// Joins outputs end to end into a single output.
+    ///
+    /// An empty slice gives empty mono audio at [`SAMPLE_RATE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the outputs differ in sample rate or channel
+    /// count.
+    pub fn concat(outputs: &[AudioOutput]) -> Result<AudioOutput> {
+        let Some((first, rest)) = outputs.split_first() else {
+            return Ok(Self::new(Vec::new(), SAMPLE_RATE, 1));
+        };
+        let mut joined = first.clone();
+        for output in rest {
+            joined.append(output)?;
+        }
+        Ok(joined)
+    }
+
+    /// Mixes `other`, scaled by `gain`, into a copy of this audio.
+    ///
+    /// The result is as long as the longer of the two; the shorter one is
+    /// padded with silence. Sums beyond the 16-bit range are clamped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the outputs differ in sample rate or channel
+    /// count.
+    pub fn mix(&self, other: &AudioOutput, gain: f32) -> Result<AudioOutput> {
+        self.check_same_format(other)?;
+        let len = self.samples.len().max(other.samples.len());
+        let samples = (0..len)
+            .map(|i| {
+                let a = self.samples.get(i).copied().unwrap_or(0) as f32;
+                let b = other.samples.get(i).copied().unwrap_or(0) as f32;
+                (a + b * gain).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16
+            })
+            .collect();
+        Ok(Self::new(samples, self.sample_rate, self.channels))
+    }
+
+    /// Multiplies every sample by `factor`, clamping to the 16-bit range.
+    pub fn apply_gain(&mut self, factor: f32) {
+        for sample in &mut self.samples {
+            *sample = (*sample as f32 * factor).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+    }
+
+    /// Scales the audio so its loudest sample reaches `target_peak`
+    /// (0.0 to 1.0 relative to full scale).
+    ///
+    /// Does nothing to silent or empty audio.
+    pub fn normalize_peak(&mut self, target_peak: f32) {
+        let peak = self.samples.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+        if peak > 0 {
+            self.apply_gain(target_peak.clamp(0.0, 1.0) * i16::MAX as f32 / peak as f32);
+        }
+    }
+
+    /// Scales the audio so its RMS level reaches `target_rms` (0.0 to 1.0
+    /// relative to full scale), for matching loudness across clips.
+    ///
+    /// Samples pushed past full scale are clamped, so a high target on
+    /// peaky audio lands below it. Does nothing to silent or empty audio.
+    pub fn normalize_rms(&mut self, target_rms: f32) {
+        if self.samples.is_empty() {
+            return;
+        }
+        let sum: f64 = self.samples.iter().map(|&s| (s as f64).powi(2)).sum();
+        let rms = (sum / self.samples.len() as f64).sqrt();
+        if rms > 0.0 {
+            self.apply_gain((target_rms.clamp(0.0, 1.0) as f64 * i16::MAX as f64 / rms) as f32);
+        }
+    }
+
+    /// Ramps the start of the audio up linearly from silence over
+    /// `duration_ms`, or over the whole audio if it is shorter.
+    pub fn fade_in(&mut self, duration_ms: u32) {
+        let fade = self.fade_frames(duration_ms);
+        let channels = self.channels.max(1) as usize;
+        for (i, frame) in self.samples.chunks_mut(channels).take(fade).enumerate() {
+            let gain = i as f32 / fade as f32;
+            for sample in frame {
+                *sample = (*sample as f32 * gain) as i16;
+            }
+        }
+    }
+
+    /// Ramps the end of the audio down linearly to silence over
+    /// `duration_ms`, or over the whole audio if it is shorter.
+    pub fn fade_out(&mut self, duration_ms: u32) {
+        let fade = self.fade_frames(duration_ms);
+        if fade == 0 {
+            return;
+        }
+        let channels = self.channels.max(1) as usize;
+        let frames = self.samples.len() / channels;
+        let start = (frames - fade) * channels;
+        let mut faded = self.samples[start..].chunks_exact_mut(channels);
+        for (i, frame) in faded.by_ref().enumerate() {
+            let gain = 1.0 - (i + 1) as f32 / fade as f32;
+            for sample in frame {
+                *sample = (*sample as f32 * gain) as i16;
+            }
+        }
+        // A trailing partial frame comes after the fade, so it is silenced
+        faded.into_remainder().fill(0);
+    }
+
+    /// Number of frames in a fade of the given length, at most the whole audio.
+    fn fade_frames(&self, duration_ms: u32) -> usize {
+        let frames = self.samples.len() / self.channels.max(1) as usize;
+        let fade = (duration_ms as f64 / 1000.0 * self.sample_rate as f64) as usize;
+        fade.min(frames)
+    }
+
+    /// Ensures two outputs share a sample rate and channel count.
+    fn check_same_format(&self, other: &AudioOutput) -> Result<()> {
+        if self.sample_rate != other.sample_rate || self.channels != other.channels {
+            return Err(SynthesizerError::AudioError(format!(
+                "cannot combine {} Hz x{} audio with {} Hz x{} audio",
+                self.sample_rate, self.channels, other.sample_rate, other.channels
+            )));
+        }
+        Ok(())
+    }
+
+    /// Ensures two outputs can be compared sample by sample.
+    fn check_comparable(&self, other: &AudioOutput) -> Result<()> {
+        self.check_same_format(other)?;
+        if self.samples.len() != other.samples.len() {
+            return Err(SynthesizerError::AudioError(format!(
+                "cannot compare {} samples with {} samples",
+                self.samples.len(),
+                other.samples.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the audio resampled to `target_rate` by linear interpolation.
+    ///
+    /// The duration is preserved to within one frame and channels are
+    /// interpolated independently. There is no anti-aliasing filter, so
+    /// downsampling folds content above the new Nyquist frequency back into
+    /// the band. Returns a copy when the rate already matches, and empty
+    /// audio if either rate is zero.
+    pub fn resample(&self, target_rate: u32) -> AudioOutput {
+        if target_rate == self.sample_rate {
+            return self.clone();
+        }
+        let channels = self.channels.max(1) as usize;
+        let frames = self.samples.len() / channels;
+        if self.sample_rate == 0 || target_rate == 0 || frames == 0 {
+            return Self::new(Vec::new(), target_rate, self.channels);
+        }
+
+        let step = self.sample_rate as f64 / target_rate as f64;
+        let out_frames = (frames as f64 / step).round() as usize;
+        let mut samples = Vec::with_capacity(out_frames * channels);
+        for frame in 0..out_frames {
+            let pos = frame as f64 * step;
+            let index = (pos as usize).min(frames - 1);
+            let next = (index + 1).min(frames - 1);
+            let frac = pos - index as f64;
+            for channel in 0..channels {
+                let a = self.samples[index * channels + channel] as f64;
+                let b = self.samples[next * channels + channel] as f64;
+                samples.push((a + (b - a) * frac).round() as i16);
+            }
+        }
+        Self::new(samples, target_rate, self.channels)
+    }
+
+    /// Removes leading and trailing silence.
+    ///
+    /// Frames where every channel's absolute value is below `threshold`
+    /// count as silent. About 5 ms of the quiet audio is kept before the
+    /// first and after the last loud frame so soft onsets are not clipped.
+    /// Audio that is silent throughout becomes empty.
+    pub fn trim_silence(&mut self, threshold: i16) {
+        let channels = self.channels.max(1) as usize;
+        let threshold = threshold.unsigned_abs();
+        let is_loud = |frame: &[i16]| frame.iter().any(|s| s.unsigned_abs() >= threshold);
+        let frames: Vec<&[i16]> = self.samples.chunks(channels).collect();
+
+        let Some(first) = frames.iter().position(|f| is_loud(f)) else {
+            self.samples.clear();
+            return;
+        };
+        let last = frames.iter().rposition(|f| is_loud(f)).unwrap_or(first);
+        let padding = (TRIM_PADDING_SECS * self.sample_rate as f64) as usize;
+        let start = first.saturating_sub(padding) * channels;
+        let end = ((last + 1 + padding) * channels).min(self.samples.len());
+
+        self.samples.truncate(end);
+        self.samples.drain(..start);
+    }
+
+    /// Returns a copy with leading and trailing silence removed (see
+    /// [`trim_silence`](Self::trim_silence)).
+    #[must_use]
+    pub fn trimmed(&self, threshold: i16) -> AudioOutput {
+        let mut audio = self.clone();
+        audio.trim_silence(threshold);
+        audio
+    }
+
+    /// Applies a brickwall peak limiter.
+    ///
+    /// Samples that would exceed `ceiling` (0.0 to 1.0 relative to full scale)
+    /// instantly pull the gain down so they land on the ceiling; the gain then
+    /// recovers linearly back to unity over `release_ms`. There is no
+    /// look-ahead, so audio before a transient is never modified.
+    pub fn limit(&mut self, ceiling: f32, release_ms: u32) {
+        let ceiling = ceiling.clamp(0.0, 1.0) * i16::MAX as f32;
+        let release_samples =
+            release_ms as f32 / 1000.0 * self.sample_rate as f32 * self.channels as f32;
+        let recovery = 1.0 / release_samples.max(1.0);
+        let mut gain = 1.0f32;
+
+        for sample in &mut self.samples {
+            let level = (*sample as f32).abs();
+            if level * gain > ceiling {
+                gain = ceiling / level;
+            }
+            if gain < 1.0 {
+                *sample = (*sample as f32 * gain) as i16;
+                gain = (gain + recovery).min(1.0);
+            }
+        }
+    }
+}
+
+/// Where one phoneme of a phoneme string lies in the rendered audio.
+///
+/// Phonemes joined by a crossfade overlap, so one phoneme's end may fall
+/// after the next one's start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhonemeTiming {
+    /// The phoneme symbol, or `_` for a word gap.
+    pub symbol: String,
+    /// Index of the first sample of the phoneme.
+    pub start_sample: usize,
+    /// Index one past the last sample of the phoneme.
+    pub end_sample: usize,
 }
 
 /// Configuration for the formant synthesizer.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SynthesisConfig {
     /// Base pitch frequency (F0) in Hz.
     pub pitch_hz: f32,
@@ -53,6 +471,134 @@ pub struct SynthesisConfig {
     pub volume: f32,
     /// Sample rate in Hz.
     pub sample_rate: u32,
+    /// Keep resonator state continuous across adjacent voiced phonemes.
+    ///
+    /// When enabled (the default), formant targets glide from the previous
+    /// voiced phoneme instead of jumping, and the attack/decay ramps are
+    /// skipped at voiced-to-voiced boundaries so the filters ring through
+    /// the join.
+    pub continuous_resonators: bool,
+    /// Pause inserted between words in milliseconds (scaled by `rate`).
+    pub word_gap_ms: u32,
+    /// Apply the per-phoneme attack/decay amplitude envelope.
+    ///
+    /// Disable when joining or crossfading segments externally so phonemes
+    /// are rendered at steady amplitude.
+    pub apply_envelope: bool,
+    /// Output headroom in dB (zero or negative).
+    ///
+    /// A fixed attenuation applied to every phoneme so constructive F1-F3
+    /// sums stay below full scale before PCM conversion. Unlike normalizing
+    /// the finished audio it does not adapt to the signal, so a normalization
+    /// step applied afterwards will re-scale the level and undo it.
+    pub headroom_db: f32,
+    /// Where a diphthong's transition is centred, as a fraction (0-1) of
+    /// its duration.
+    ///
+    /// At 0.5 the formants glide evenly from the first target to the second;
+    /// lower values reach the second target sooner, higher values hold the
+    /// first target longer.
+    pub diphthong_glide_point: f32,
+    /// Shortest duration a fricative is compressed to by fast rates
+    /// (milliseconds, 0 = no floor).
+    ///
+    /// Fricatives such as /s/ carry much of the intelligibility of fast
+    /// speech. The floor never lengthens a fricative beyond its base
+    /// duration.
+    pub fricative_min_ms: u32,
+    /// Amount of aspiration noise mixed into the voiced source (0.0 = none).
+    ///
+    /// The noise is added before the formant resonators, so it takes on the
+    /// vowel's formant shape. Applies to vowels, nasals and voiced
+    /// approximants.
+    pub breathiness: f32,
+    /// Tilt of the voiced source spectrum (-1.0 to 1.0, 0 = unchanged).
+    ///
+    /// Positive values pass the source through a one-pole low-pass, whose
+    /// coefficient is the tilt, for a darker, softer voice. Negative values
+    /// brighten it by adding back what a gentle low-pass would remove.
+    /// Applies to vowels, nasals and voiced approximants.
+    pub spectral_tilt: f32,
+    /// Length of the crossfade between adjacent voiced and voiceless
+    /// sounds, such as a vowel running into /s/ (milliseconds, 0 = off).
+    ///
+    /// The two phonemes overlap by this much with an equal-power crossfade
+    /// instead of each ramping to silence, so the voicing tapers out while
+    /// the noise comes in. Stops and affricates are not crossfaded; their
+    /// closure already separates them from their neighbours.
+    pub voicing_crossfade_ms: u32,
+    /// Length of the crossfade between adjacent joined voiced phonemes when
+    /// `continuous_resonators` is enabled (milliseconds, 0 = off).
+    ///
+    /// Pitch phase and resonator state already carry across the join, but
+    /// each phoneme mixes the resonators differently, so the output level
+    /// would otherwise step at the boundary and click.
+    pub resonant_crossfade_ms: u32,
+    /// Longest pause a run of consecutive word gaps adds up to
+    /// (milliseconds, scaled by `rate`; 0 = no cap).
+    ///
+    /// Stacked breaks, such as a paragraph break after a sentence pause,
+    /// otherwise sum to an unnaturally long silence.
+    pub max_pause_ms: u32,
+    /// Glide the formants of a vowel that follows a consonant in from the
+    /// consonant's locus rather than starting at the vowel's steady state.
+    ///
+    /// The transition lasts about 30 ms (scaled by `rate`) and starts F2
+    /// low after labials, mid after alveolars and high after velars.
+    pub coarticulation: bool,
+    /// Length of the breathy noise after a voiceless stop that starts a
+    /// syllable, as in English "pin" but not "spin" (milliseconds, scaled
+    /// by `rate`; 0 = off).
+    ///
+    /// [`from_voice`](Self::from_voice) turns it off for languages whose
+    /// stops are unaspirated (Spanish, French).
+    pub aspiration_ms: u32,
+    /// How strongly a vowel before a nasal consonant is nasalized (0.0 =
+    /// off, 1.0 = fully nasal where it meets the consonant).
+    ///
+    /// Over the last 40% of the vowel the nasal resonator is gradually
+    /// mixed in and F1 widens, as the velum lowers ahead of /m/, /n/ or /ŋ/.
+    pub nasalization: f32,
+    /// Frequency of the fixed fourth formant (Hz, 0 = off).
+    ///
+    /// F4 and F5 barely move between vowels but follow vocal-tract length;
+    /// [`from_voice`](Self::from_voice) and [`female`](Self::female) raise
+    /// them for higher voices. They are mixed in at low level for vowels
+    /// and approximants, and dropped if above 45% of the sample rate.
+    pub f4_hz: f32,
+    /// Frequency of the fixed fifth formant (Hz, 0 = off). See `f4_hz`.
+    pub f5_hz: f32,
+    /// Factor applied to F1-F3 of every phoneme and to consonant loci,
+    /// modelling vocal-tract length (1.0 = adult male; see
+    /// [`VoiceVariant::formant_scale`](crate::VoiceVariant::formant_scale)).
+    pub formant_scale: f32,
+    /// Scale applied to pitch movement from sentence intonation, stress and
+    /// emphasis (1.0 = normal, 0.0 = monotone).
+    pub pitch_range: f32,
+    /// Scale applied to the pitch and volume boost of emphasized words
+    /// (1.0 = normal, 0.0 = none).
+    pub emphasis_scale: f32,
+    /// Random cycle-to-cycle variation of the pitch period (fraction of F0,
+    /// 0 = perfectly periodic).
+    pub jitter: f32,
+    /// Random cycle-to-cycle variation of the glottal pulse amplitude
+    /// (fraction, 0 = constant).
+    pub shimmer: f32,
+    /// Seed of the noise generator, which drives fricative and aspiration
+    /// noise as well as jitter and shimmer.
+    ///
+    /// With `Some`, identical configurations produce byte-identical audio,
+    /// which suits caching and golden tests. With `None`, each synthesizer
+    /// (and each reset) draws a fresh seed from the clock and a per-thread
+    /// random source, so repeated renderings vary. Default is a fixed seed.
+    pub noise_seed: Option<u32>,
+    /// Whisper: excite vowels, nasals and approximants with noise instead
+    /// of the glottal source, and drop the voicing of voiced consonants, so
+    /// no periodic pitch remains.
+    pub whisper: bool,
+    /// Hold `pitch_hz` constant: sentence intonation, stress, emphasis,
+    /// per-phoneme pitch overrides and jitter are all ignored.
+    pub monotone: bool,
 }
 
 impl Default for SynthesisConfig {
@@ -62,11 +608,62 @@ impl Default for SynthesisConfig {
             rate: 1.0,
             volume: 0.8,
             sample_rate: SAMPLE_RATE,
+            continuous_resonators: true,
+            word_gap_ms: 100,
+            apply_envelope: true,
+            headroom_db: -3.0,
+            diphthong_glide_point: 0.5,
+            fricative_min_ms: 0,
+            breathiness: 0.0,
+            spectral_tilt: 0.0,
+            voicing_crossfade_ms: 10,
+            resonant_crossfade_ms: 5,
+            max_pause_ms: 0,
+            coarticulation: true,
+            aspiration_ms: ASPIRATION_MS,
+            nasalization: 0.3,
+            f4_hz: F4_HZ,
+            f5_hz: F5_HZ,
+            formant_scale: 1.0,
+            pitch_range: 1.0,
+            emphasis_scale: 1.0,
+            jitter: 0.01,
+            shimmer: 0.03,
+            noise_seed: Some(NOISE_SEED),
+            whisper: false,
+            monotone: false,
         }
     }
 }
 
 impl SynthesisConfig {
+    /// Creates the engine configuration for a high-level voice configuration.
+    ///
+    /// This is the mapping `Synthesizer` uses internally; fields without a
+    /// `VoiceConfig` counterpart keep their defaults.
+    pub fn from_voice(voice: &VoiceConfig) -> Self {
+        Self {
+            pitch_hz: voice.effective_pitch_hz(),
+            rate: voice.rate_multiplier(),
+            volume: voice.volume_level().min(1.0),
+            sample_rate: voice.sample_rate.clamp(MIN_SAMPLE_RATE, MAX_SAMPLE_RATE),
+            breathiness: voice.breathiness,
+            spectral_tilt: voice.spectral_tilt,
+            aspiration_ms: match voice.language {
+                Language::English => ASPIRATION_MS,
+                Language::Spanish | Language::French => 0,
+            },
+            formant_scale: voice.variant.formant_scale(),
+            pitch_range: voice.intonation.pitch_range(),
+            emphasis_scale: voice.intonation.emphasis_scale(),
+            noise_seed: voice.noise_seed,
+            whisper: voice.whisper,
+            monotone: voice.monotone,
+            ..Default::default()
+        }
+        .with_high_formants_for_pitch()
+    }
+
     /// Creates a configuration for a male voice.
     pub fn male() -> Self {
         Self {
@@ -81,6 +678,23 @@ impl SynthesisConfig {
             pitch_hz: 200.0,
             ..Default::default()
         }
+        .with_high_formants_for_pitch()
+    }
+
+    /// Sets a fixed noise generator seed, making output reproducible.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.noise_seed = Some(seed);
+        self
+    }
+
+    /// Sets F4 and F5 for the vocal-tract length suggested by `pitch_hz`:
+    /// the defaults at 120 Hz, higher for higher voices.
+    fn with_high_formants_for_pitch(mut self) -> Self {
+        let scale = (self.pitch_hz / HIGH_FORMANT_REFERENCE_PITCH_HZ).max(0.1).powf(0.25);
+        self.f4_hz = F4_HZ * scale;
+        self.f5_hz = F5_HZ * scale;
+        self
     }
 }
 
@@ -119,7 +733,6 @@ impl Resonator {
     }
 
     /// Reset the resonator state.
-    #[allow(dead_code)]
     fn reset(&mut self) {
         self.y1 = 0.0;
         self.y2 = 0.0;
@@ -138,39 +751,303 @@ pub struct FormantSynthesizer {
     config: SynthesisConfig,
     /// Formant resonators (F1, F2, F3).
     formants: [Resonator; 3],
+    /// Fixed high formant resonators (F4, F5) and their mix weights; a
+    /// weight is 0 for a formant that is off.
+    high_formants: [(Resonator, f32); 2],
     /// Nasal resonator.
     nasal: Resonator,
+    /// Resonator shaping the aspiration noise after voiceless stops.
+    aspiration: Resonator,
+    /// Low-passed voiced source, for spectral tilt.
+    tilt_state: f32,
     /// Current pitch phase.
     pitch_phase: f32,
+    /// Pitch factor of the current glottal cycle (jitter).
+    cycle_pitch: f32,
+    /// Amplitude factor of the current glottal cycle (shimmer).
+    cycle_amplitude: f32,
     /// Noise generator state.
     noise_state: u32,
+    /// Formant targets of the previous phoneme, if it was voiced and resonant.
+    prev_formants: Option<FormantValues>,
+    /// F2 locus of the previous phoneme, if it was a consonant.
+    prev_locus: Option<f32>,
+    /// Skip the attack ramp of the current phoneme.
+    join_prev: bool,
+    /// Skip the decay ramp of the current phoneme.
+    join_next: bool,
+    /// Follow the burst of the current voiceless stop with aspiration.
+    aspirate: bool,
+    /// Nasalize the end of the current vowel, which precedes a nasal.
+    nasalize: bool,
+    /// Last sample rendered (before headroom), faded out by plosive closures.
+    last_sample: f32,
+}
+
+/// Default seed of the noise generator.
+pub(crate) const NOISE_SEED: u32 = 12345;
+
+/// Returns the given seed, or a fresh one from the clock and a per-thread
+/// random source if none is set.
+fn resolve_seed(seed: Option<u32>) -> u32 {
+    seed.unwrap_or_else(|| {
+        let mut hasher = RandomState::new().build_hasher();
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+        hasher.write_u128(nanos);
+        hasher.finish() as u32
+    })
+}
+
+/// Length of the formant glide into a joined voiced phoneme (seconds).
+const RESONATOR_GLIDE_SECS: f32 = 0.02;
+
+/// Length of the formant transition from a consonant locus into a vowel
+/// (seconds, before rate scaling).
+const COARTICULATION_SECS: f32 = 0.03;
+
+/// Fraction of the distance from a vowel's F2 to the consonant locus at
+/// which the transition starts.
+const LOCUS_ONSET: f32 = 0.6;
+
+/// Length of the fade from the preceding audio into a plosive closure (seconds).
+const CLOSURE_FADE_SECS: f32 = 0.002;
+
+/// Length of the attack ramp on a plosive burst (seconds).
+const BURST_ATTACK_SECS: f32 = 0.0005;
+
+/// Default length of the aspiration after an onset voiceless stop (milliseconds).
+const ASPIRATION_MS: u32 = 40;
+
+/// Centre frequency and bandwidth of the aspiration noise (Hz).
+const ASPIRATION_HZ: f32 = 3000.0;
+const ASPIRATION_BANDWIDTH: f32 = 1000.0;
+
+/// Level of the aspiration noise relative to full scale.
+const ASPIRATION_LEVEL: f32 = 0.4;
+
+/// Default frequencies of the fixed high formants (Hz), for a voice
+/// pitched at `HIGH_FORMANT_REFERENCE_PITCH_HZ`.
+const F4_HZ: f32 = 3500.0;
+const F5_HZ: f32 = 4500.0;
+const HIGH_FORMANT_REFERENCE_PITCH_HZ: f32 = 120.0;
+
+/// Bandwidths (Hz) and mix weights of F4 and F5.
+const HIGH_FORMANT_BANDWIDTHS: [f32; 2] = [250.0, 300.0];
+const HIGH_FORMANT_WEIGHTS: [f32; 2] = [0.12, 0.06];
+
+/// Centre frequency and bandwidth of the nasal resonator (Hz).
+const NASAL_HZ: f32 = 250.0;
+const NASAL_BANDWIDTH: f32 = 100.0;
+
+/// Fraction at the end of a vowel that is nasalized before a nasal consonant.
+const NASALIZED_FRACTION: f32 = 0.4;
+
+/// Level of the noise replacing the glottal source when whispering.
+const WHISPER_LEVEL: f32 = 0.5;
+
+/// Largest low-pass coefficient used for spectral tilt, keeping the filter
+/// from muting the source.
+const MAX_SPECTRAL_TILT: f32 = 0.95;
+
+/// Rate of the tongue taps in a trill (Hz), independent of pitch.
+const TRILL_HZ: f32 = 27.0;
+
+/// How far the amplitude dips at each tap of a trill (0-1).
+const TRILL_DEPTH: f32 = 0.8;
+
+/// Duration, pitch and volume factors applied to vowels in a syllable
+/// with the given stress.
+fn stress_factors(stress: StressLevel) -> (f32, f32, f32) {
+    match stress {
+        StressLevel::Primary => (1.3, 1.1, 1.15),
+        StressLevel::Secondary => (1.12, 1.04, 1.05),
+        StressLevel::Unstressed => (1.0, 1.0, 1.0),
+    }
+}
+
+/// Volume factor for emphasized (shouted) words.
+const EMPHASIS_VOLUME: f32 = 1.4;
+/// Pitch factor for emphasized (shouted) words.
+const EMPHASIS_PITCH: f32 = 1.12;
+/// Pitch factor for words of quoted speech.
+const QUOTE_PITCH: f32 = 1.08;
+/// Pitch factor for words of a parenthetical phrase.
+const PARENTHETICAL_PITCH: f32 = 0.9;
+/// Duration factor for words of a parenthetical phrase.
+const PARENTHETICAL_DURATION: f32 = 0.85;
+
+/// Relative pitch fall across a sentence, from its first phoneme to its last.
+const DECLINATION: f32 = 0.1;
+/// Relative pitch rise at the end of a question.
+const QUESTION_RISE: f32 = 0.3;
+/// Relative pitch fall at the end of other sentences.
+const FINAL_FALL: f32 = 0.2;
+/// Fraction of a sentence, counted from its end, over which the final rise
+/// or fall happens.
+const FINAL_SPAN: f32 = 0.3;
+
+/// Pitch factor for every symbol from the sentence intonation.
+///
+/// Sentences end at a `?` or `|` token, or at the end of the sequence.
+/// Pitch declines gently across each sentence, then questions (ending in
+/// `?`) rise over their final stretch and other sentences fall. Positions
+/// are counted in phonemes, so the contour does not depend on the speech
+/// rate.
+pub(crate) fn sentence_contour(symbols: &[&str], inventory: &PhonemeInventory) -> Vec<f32> {
+    let mut contour = vec![1.0; symbols.len()];
+    let mut start = 0;
+    for end in 0..=symbols.len() {
+        let mark = symbols.get(end).copied();
+        if mark.is_some_and(|m| m != QUESTION_MARK && m != STATEMENT_MARK) {
+            continue;
+        }
+
+        let question = mark == Some(QUESTION_MARK);
+        let phonemes: Vec<usize> = (start..end)
+            .filter(|&i| {
+                let symbol = split_length_mark(symbols[i]).0;
+                inventory.get(symbol).is_some_and(|p| p.category != PhonemeCategory::Silence)
+            })
+            .collect();
+        let last = phonemes.len().saturating_sub(1).max(1) as f32;
+        let final_change = if question { QUESTION_RISE } else { -FINAL_FALL };
+        let final_start = 1.0 - FINAL_SPAN;
+        for (k, &i) in phonemes.iter().enumerate() {
+            let pos = k as f32 / last;
+            contour[i] = 1.0 + DECLINATION * (0.5 - pos);
+            if pos > final_start {
+                contour[i] += final_change * (pos - final_start) / FINAL_SPAN;
+            }
+        }
+        start = end + 1;
+    }
+    contour
+}
+
+/// Returns true for the syllable nuclei that carry stress.
+fn is_stress_bearing(phoneme: &Phoneme) -> bool {
+    matches!(phoneme.category, PhonemeCategory::Vowel | PhonemeCategory::Diphthong)
+}
+
+/// Returns true if two adjacent phonemes switch between voiced and
+/// voiceless excitation without a stop closure in between.
+fn is_voicing_transition(prev: &Phoneme, next: &Phoneme) -> bool {
+    let continuant = |p: &Phoneme| {
+        !matches!(
+            p.category,
+            PhonemeCategory::Plosive | PhonemeCategory::Affricate | PhonemeCategory::Silence
+        )
+    };
+    continuant(prev) && continuant(next) && prev.voiced != next.voiced
+}
+
+/// Splits a trailing length mark (`:`) off a phoneme symbol.
+fn split_length_mark(symbol: &str) -> (&str, bool) {
+    match symbol.strip_suffix(':') {
+        Some(base) if !base.is_empty() => (base, true),
+        _ => (symbol, false),
+    }
+}
+
+/// F2 locus (Hz) that formant transitions into a following vowel point to,
+/// by the consonant's place of articulation.
+fn f2_locus(phoneme: &Phoneme) -> Option<f32> {
+    if matches!(
+        phoneme.category,
+        PhonemeCategory::Vowel | PhonemeCategory::Diphthong | PhonemeCategory::Silence
+    ) {
+        return None;
+    }
+    match phoneme.symbol {
+        // Labials
+        "p" | "b" | "m" | "f" | "v" | "w" => Some(800.0),
+        // Dentals and alveolars
+        "t" | "d" | "n" | "s" | "z" | "T" | "D" | "l" | "r" | "rr" | "R" => Some(1800.0),
+        // Velars
+        "k" | "g" | "N" | "x" => Some(2300.0),
+        _ => None,
+    }
+}
+
+/// The phoneme of the next symbol that is not a stress mark, if any.
+fn next_phoneme<'a>(symbols: &[&str], inventory: &'a PhonemeInventory) -> Option<&'a Phoneme> {
+    symbols
+        .iter()
+        .find(|next| StressLevel::from_mark(next).is_none())
+        .and_then(|next| inventory.get(split_length_mark(next).0))
+}
+
+/// Formants with F1-F3 multiplied by `scale`; bandwidths are unchanged.
+fn scale_formants(formants: &FormantValues, scale: f32) -> FormantValues {
+    FormantValues {
+        f1: formants.f1 * scale,
+        f2: formants.f2 * scale,
+        f3: formants.f3 * scale,
+        ..*formants
+    }
+}
+
+/// Returns true for a trilled /r/ (Spanish "rr").
+fn is_trill(phoneme: &Phoneme) -> bool {
+    phoneme.category == PhonemeCategory::Rhotic && phoneme.symbol == "rr"
+}
+
+/// Returns true if the phoneme is voiced and shaped by the formant resonators.
+fn is_resonant(phoneme: &Phoneme) -> bool {
+    phoneme.voiced && phoneme.formants.is_some()
 }
 
 impl FormantSynthesizer {
     /// Creates a new formant synthesizer.
     pub fn new(config: SynthesisConfig) -> Self {
         let sr = config.sample_rate as f32;
+        let high_formant = |i: usize, freq: f32| {
+            let weight = if freq > 0.0 && freq < 0.45 * sr { HIGH_FORMANT_WEIGHTS[i] } else { 0.0 };
+            (Resonator::new(freq, HIGH_FORMANT_BANDWIDTHS[i], sr), weight)
+        };
         Self {
-            config,
             formants: [
                 Resonator::new(500.0, 60.0, sr),
                 Resonator::new(1500.0, 90.0, sr),
                 Resonator::new(2500.0, 150.0, sr),
             ],
+            high_formants: [high_formant(0, config.f4_hz), high_formant(1, config.f5_hz)],
+            noise_state: resolve_seed(config.noise_seed),
+            config,
             nasal: Resonator::new(300.0, 100.0, sr),
+            aspiration: Resonator::new(ASPIRATION_HZ, ASPIRATION_BANDWIDTH, sr),
+            tilt_state: 0.0,
             pitch_phase: 0.0,
-            noise_state: 12345,
+            cycle_pitch: 1.0,
+            cycle_amplitude: 1.0,
+            prev_formants: None,
+            prev_locus: None,
+            join_prev: false,
+            join_next: false,
+            aspirate: false,
+            nasalize: false,
+            last_sample: 0.0,
         }
     }
 
-    /// Reset the synthesizer state.
-    #[allow(dead_code)]
+    /// Reset the synthesizer state, including the noise generator.
     pub fn reset(&mut self) {
         for f in &mut self.formants {
             f.reset();
         }
+        for (f, _) in &mut self.high_formants {
+            f.reset();
+        }
         self.nasal.reset();
+        self.aspiration.reset();
+        self.tilt_state = 0.0;
         self.pitch_phase = 0.0;
+        self.cycle_pitch = 1.0;
+        self.cycle_amplitude = 1.0;
+        self.noise_state = resolve_seed(self.config.noise_seed);
+        self.prev_formants = None;
+        self.prev_locus = None;
+        self.last_sample = 0.0;
     }
 
     /// Generate white noise sample.
@@ -184,16 +1061,23 @@ impl FormantSynthesizer {
     /// Generate a glottal pulse waveform.
     fn glottal_pulse(&mut self, f0: f32) -> f32 {
         let sample_rate = self.config.sample_rate as f32;
-        let phase_inc = f0 / sample_rate;
+        let phase_inc = f0 * self.cycle_pitch / sample_rate;
         
         self.pitch_phase += phase_inc;
         if self.pitch_phase >= 1.0 {
             self.pitch_phase -= 1.0;
+            // Perturb the next cycle; no noise is drawn when both are off
+            if self.config.jitter > 0.0 && !self.config.monotone {
+                self.cycle_pitch = 1.0 + self.noise() * self.config.jitter;
+            }
+            if self.config.shimmer > 0.0 {
+                self.cycle_amplitude = 1.0 + self.noise() * self.config.shimmer;
+            }
         }
 
         // Modified Liljencrants-Fant glottal pulse model (simplified)
         let t = self.pitch_phase;
-        if t < 0.4 {
+        let pulse = if t < 0.4 {
             // Opening phase
             let x = t / 0.4;
             3.0 * x * x - 2.0 * x * x * x
@@ -204,14 +1088,37 @@ impl FormantSynthesizer {
         } else {
             // Closed phase
             0.0
-        }
+        };
+        pulse * self.cycle_amplitude
     }
 
     /// Synthesize audio for a single phoneme.
     pub fn synthesize_phoneme(&mut self, phoneme: &Phoneme, duration_ms: u32) -> Vec<f32> {
-        let sample_rate = self.config.sample_rate as f32;
-        let duration_samples = ((duration_ms as f32 / 1000.0) * sample_rate / self.config.rate) as usize;
-        let mut output = Vec::with_capacity(duration_samples);
+        self.render_phoneme(phoneme, duration_ms, false)
+    }
+
+    /// Synthesize a geminate (long) phoneme at twice its singleton duration.
+    ///
+    /// Stops and affricates lengthen only their closure so the release keeps
+    /// its singleton length; all other phonemes are held twice as long.
+    pub fn synthesize_geminate(&mut self, phoneme: &Phoneme, duration_ms: u32) -> Vec<f32> {
+        self.render_phoneme(phoneme, duration_ms, true)
+    }
+
+    /// Render a single phoneme, optionally as a geminate.
+    fn render_phoneme(&mut self, phoneme: &Phoneme, duration_ms: u32, geminate: bool) -> Vec<f32> {
+        let mut duration_samples = self.duration_samples(duration_ms);
+        let mut extra_closure = 0;
+        if geminate {
+            match phoneme.category {
+                PhonemeCategory::Plosive | PhonemeCategory::Affricate => extra_closure = duration_samples,
+                _ => duration_samples *= 2,
+            }
+        }
+        let mut output = Vec::with_capacity(duration_samples + extra_closure);
+        let scale = self.config.formant_scale;
+        let formants = phoneme.formants.map(|f| scale_formants(&f, scale));
+        let formants_end = phoneme.formants_end.map(|f| scale_formants(&f, scale));
 
         match phoneme.category {
             PhonemeCategory::Silence => {
@@ -219,49 +1126,176 @@ impl FormantSynthesizer {
                 output.extend(std::iter::repeat_n(0.0, duration_samples));
             }
             PhonemeCategory::Vowel | PhonemeCategory::Diphthong => {
-                if let Some(formants) = &phoneme.formants {
-                    self.synthesize_vowel(formants, duration_samples, &mut output);
+                if let Some(formants) = &formants {
+                    self.synthesize_vowel(formants, formants_end.as_ref(), duration_samples, &mut output);
                 }
             }
             PhonemeCategory::Nasal => {
-                if let Some(formants) = &phoneme.formants {
+                if let Some(formants) = &formants {
                     self.synthesize_nasal(formants, duration_samples, &mut output);
                 }
             }
             PhonemeCategory::Plosive => {
-                self.synthesize_plosive(phoneme.voiced, duration_samples, &mut output);
+                let aspirated = self.aspirate && !phoneme.voiced;
+                self.synthesize_plosive(phoneme.voiced, aspirated, duration_samples, extra_closure, &mut output);
             }
             PhonemeCategory::Fricative => {
                 self.synthesize_fricative(phoneme.voiced, duration_samples, &mut output);
             }
             PhonemeCategory::Affricate => {
-                self.synthesize_affricate(phoneme.voiced, duration_samples, &mut output);
+                self.synthesize_affricate(phoneme.voiced, duration_samples, extra_closure, &mut output);
+            }
+            PhonemeCategory::Rhotic if is_trill(phoneme) => {
+                if let Some(formants) = &formants {
+                    self.synthesize_trill(formants, duration_samples, &mut output);
+                }
             }
             PhonemeCategory::Lateral | PhonemeCategory::Rhotic | PhonemeCategory::Approximant => {
-                if let Some(formants) = &phoneme.formants {
+                if let Some(formants) = &formants {
                     self.synthesize_approximant(formants, phoneme.voiced, duration_samples, &mut output);
                 }
             }
         }
 
+        self.last_sample = output.last().copied().unwrap_or(self.last_sample);
+        let headroom = 10f32.powf(self.config.headroom_db.min(0.0) / 20.0);
+        for sample in &mut output {
+            *sample *= headroom;
+        }
+
+        self.prev_formants = if is_resonant(phoneme) {
+            formants_end.or(formants)
+        } else {
+            None
+        };
+        self.prev_locus = f2_locus(phoneme).map(|locus| locus * scale);
         output
     }
 
-    /// Synthesize a vowel sound.
-    fn synthesize_vowel(&mut self, formants: &FormantValues, samples: usize, output: &mut Vec<f32>) {
+    /// Glottal source for resonant voiced sounds, with any breathiness noise
+    /// and spectral tilt. Whispering replaces it with noise.
+    fn voiced_source(&mut self) -> f32 {
+        if self.config.whisper {
+            return self.noise() * WHISPER_LEVEL;
+        }
+
+        let pulse = self.glottal_pulse(self.config.pitch_hz);
+        let source = if self.config.breathiness > 0.0 {
+            pulse + self.noise() * self.config.breathiness
+        } else {
+            pulse
+        };
+
+        let tilt = self.config.spectral_tilt.clamp(-1.0, MAX_SPECTRAL_TILT);
+        if tilt > 0.0 {
+            self.tilt_state = (1.0 - tilt) * source + tilt * self.tilt_state;
+            self.tilt_state
+        } else if tilt < 0.0 {
+            self.tilt_state = 0.5 * source + 0.5 * self.tilt_state;
+            source - tilt * (source - self.tilt_state)
+        } else {
+            source
+        }
+    }
+
+    /// Weighted sum of the fixed high formants (F4, F5) for a source sample.
+    fn high_formants_out(&mut self, source: f32) -> f32 {
+        self.high_formants
+            .iter_mut()
+            .filter(|(_, weight)| *weight > 0.0)
+            .map(|(resonator, weight)| resonator.process(source) * *weight)
+            .sum()
+    }
+
+    /// Returns the formants to glide from and the glide length in samples,
+    /// if the current phoneme is joined to a previous voiced phoneme.
+    fn glide_from(&self, samples: usize) -> Option<(FormantValues, usize)> {
+        if !self.join_prev {
+            return None;
+        }
+        let glide = (RESONATOR_GLIDE_SECS * self.config.sample_rate as f32) as usize;
+        self.prev_formants.map(|f| (f, glide.min(samples / 3)))
+    }
+
+    /// Returns the formants to glide from and the transition length in
+    /// samples, if the current vowel follows a consonant with a known locus.
+    fn locus_onset(&self, formants: &FormantValues, samples: usize) -> Option<(FormantValues, usize)> {
+        if !self.config.coarticulation {
+            return None;
+        }
+        let len = (COARTICULATION_SECS * self.config.sample_rate as f32 / self.config.rate) as usize;
+        self.prev_locus.map(|locus| {
+            let onset = FormantValues {
+                f2: formants.f2 + (locus - formants.f2) * LOCUS_ONSET,
+                ..*formants
+            };
+            (onset, len.min(samples / 3))
+        })
+    }
+
+    /// Progress (0-1) through a diphthong's transition at position `t` (0-1).
+    ///
+    /// Piecewise linear, passing through the halfway point at the configured
+    /// glide point.
+    fn diphthong_progress(&self, t: f32) -> f32 {
+        let center = self.config.diphthong_glide_point.clamp(0.05, 0.95);
+        if t < center {
+            0.5 * t / center
+        } else {
+            0.5 + 0.5 * (t - center) / (1.0 - center)
+        }
+    }
+
+    /// Synthesize a vowel sound, gliding towards `end` for diphthongs.
+    fn synthesize_vowel(
+        &mut self,
+        formants: &FormantValues,
+        end: Option<&FormantValues>,
+        samples: usize,
+        output: &mut Vec<f32>,
+    ) {
         let sr = self.config.sample_rate as f32;
         
         // Update formant resonators
         self.formants[0].set_params(formants.f1, formants.b1, sr);
         self.formants[1].set_params(formants.f2, formants.b2, sr);
         self.formants[2].set_params(formants.f3, formants.b3, sr);
+        let glide = self.glide_from(samples).or_else(|| self.locus_onset(formants, samples));
+        let nasal_start = if self.nasalize {
+            samples - (samples as f32 * NASALIZED_FRACTION) as usize
+        } else {
+            samples
+        };
+        if nasal_start < samples {
+            self.nasal.set_params(NASAL_HZ, NASAL_BANDWIDTH, sr);
+        }
 
         for i in 0..samples {
+            let join = glide.filter(|&(_, len)| i < len);
+            let nasality = if i >= nasal_start {
+                self.config.nasalization * (i - nasal_start) as f32 / (samples - nasal_start) as f32
+            } else {
+                0.0
+            };
+            if join.is_some() || end.is_some() || nasality > 0.0 {
+                let target = match end {
+                    Some(end) => formants.lerp(end, self.diphthong_progress(i as f32 / samples as f32)),
+                    None => *formants,
+                };
+                let f = match join {
+                    Some((start, len)) => start.lerp(&target, i as f32 / len as f32),
+                    None => target,
+                };
+                self.formants[0].set_params(f.f1, f.b1 * (1.0 + 2.0 * nasality), sr);
+                self.formants[1].set_params(f.f2, f.b2, sr);
+                self.formants[2].set_params(f.f3, f.b3, sr);
+            }
+
             // Apply amplitude envelope for natural sound
             let env = self.amplitude_envelope(i, samples);
             
             // Generate glottal source
-            let source = self.glottal_pulse(self.config.pitch_hz);
+            let source = self.voiced_source();
             
             // Apply formant filtering (parallel configuration)
             let f1_out = self.formants[0].process(source);
@@ -269,8 +1303,12 @@ impl FormantSynthesizer {
             let f3_out = self.formants[2].process(source);
             
             // Mix formants with decreasing weights
-            let sample = (f1_out * 1.0 + f2_out * 0.5 + f3_out * 0.25) * env * self.config.volume;
-            output.push(sample);
+            let mut sample = f1_out * 1.0 + f2_out * 0.5 + f3_out * 0.25 + self.high_formants_out(source);
+            if nasality > 0.0 {
+                let nasal_out = self.nasal.process(source);
+                sample = sample * (1.0 - nasality) + nasal_out * nasality;
+            }
+            output.push(sample * env * self.config.volume);
         }
     }
 
@@ -279,11 +1317,17 @@ impl FormantSynthesizer {
         let sr = self.config.sample_rate as f32;
         
         self.formants[0].set_params(formants.f1, formants.b1 * 1.5, sr);
-        self.nasal.set_params(250.0, 100.0, sr);
+        self.nasal.set_params(NASAL_HZ, NASAL_BANDWIDTH, sr);
+        let glide = self.glide_from(samples);
 
         for i in 0..samples {
+            if let Some((start, len)) = glide.filter(|&(_, len)| i < len) {
+                let f = start.lerp(formants, i as f32 / len as f32);
+                self.formants[0].set_params(f.f1, f.b1 * 1.5, sr);
+            }
+
             let env = self.amplitude_envelope(i, samples);
-            let source = self.glottal_pulse(self.config.pitch_hz);
+            let source = self.voiced_source();
             
             let formant_out = self.formants[0].process(source);
             let nasal_out = self.nasal.process(source);
@@ -294,17 +1338,38 @@ impl FormantSynthesizer {
     }
 
     /// Synthesize a plosive sound.
-    fn synthesize_plosive(&mut self, voiced: bool, samples: usize, output: &mut Vec<f32>) {
+    ///
+    /// `extra_closure` lengthens the closure silence (for geminates) without
+    /// changing the burst. The closure fades in from the preceding sample and
+    /// the burst ramps up from zero, so neither boundary clicks. An
+    /// `aspirated` stop adds `aspiration_ms` of band-passed noise after the
+    /// burst.
+    fn synthesize_plosive(
+        &mut self,
+        voiced: bool,
+        aspirated: bool,
+        samples: usize,
+        extra_closure: usize,
+        output: &mut Vec<f32>,
+    ) {
+        let sr = self.config.sample_rate as f32;
+
         // Closure phase (silence)
         let closure_samples = samples * 2 / 3;
-        output.extend(std::iter::repeat_n(0.0, closure_samples));
+        let fade = ((CLOSURE_FADE_SECS * sr) as usize).min(closure_samples);
+        for i in 0..fade {
+            output.push(self.last_sample * (1.0 - (i + 1) as f32 / fade as f32));
+        }
+        output.extend(std::iter::repeat_n(0.0, closure_samples - fade + extra_closure));
 
         // Burst phase
         let burst_samples = samples - closure_samples;
+        let attack = ((BURST_ATTACK_SECS * sr) as usize).clamp(1, burst_samples.max(1));
         for i in 0..burst_samples {
-            let env = (1.0 - i as f32 / burst_samples as f32).powi(2);
+            let ramp = ((i + 1) as f32 / attack as f32).min(1.0);
+            let env = (1.0 - i as f32 / burst_samples as f32).powi(2) * ramp;
             let noise = self.noise();
-            let voicing = if voiced {
+            let voicing = if voiced && !self.config.whisper {
                 self.glottal_pulse(self.config.pitch_hz) * 0.3
             } else {
                 0.0
@@ -313,6 +1378,17 @@ impl FormantSynthesizer {
             let sample = (noise * 0.4 + voicing) * env * self.config.volume;
             output.push(sample);
         }
+
+        if aspirated {
+            let aspiration_samples = self.duration_samples(self.config.aspiration_ms);
+            for i in 0..aspiration_samples {
+                let t = i as f32 / aspiration_samples as f32;
+                let env = (t * 8.0).min(1.0) * (1.0 - t);
+                let noise = self.noise();
+                let sample = self.aspiration.process(noise) * ASPIRATION_LEVEL * env * self.config.volume;
+                output.push(sample);
+            }
+        }
     }
 
     /// Synthesize a fricative sound.
@@ -320,7 +1396,7 @@ impl FormantSynthesizer {
         for i in 0..samples {
             let env = self.amplitude_envelope(i, samples);
             let noise = self.noise();
-            let voicing = if voiced {
+            let voicing = if voiced && !self.config.whisper {
                 self.glottal_pulse(self.config.pitch_hz) * 0.4
             } else {
                 0.0
@@ -332,10 +1408,10 @@ impl FormantSynthesizer {
     }
 
     /// Synthesize an affricate sound.
-    fn synthesize_affricate(&mut self, voiced: bool, samples: usize, output: &mut Vec<f32>) {
+    fn synthesize_affricate(&mut self, voiced: bool, samples: usize, extra_closure: usize, output: &mut Vec<f32>) {
         // Plosive portion
         let plosive_samples = samples / 3;
-        self.synthesize_plosive(voiced, plosive_samples, output);
+        self.synthesize_plosive(voiced, false, plosive_samples, extra_closure, output);
         
         // Fricative portion
         let fricative_samples = samples - plosive_samples;
@@ -348,12 +1424,19 @@ impl FormantSynthesizer {
         
         self.formants[0].set_params(formants.f1, formants.b1 * 1.2, sr);
         self.formants[1].set_params(formants.f2, formants.b2 * 1.2, sr);
+        let glide = self.glide_from(samples);
 
         for i in 0..samples {
+            if let Some((start, len)) = glide.filter(|&(_, len)| i < len) {
+                let f = start.lerp(formants, i as f32 / len as f32);
+                self.formants[0].set_params(f.f1, f.b1 * 1.2, sr);
+                self.formants[1].set_params(f.f2, f.b2 * 1.2, sr);
+            }
+
             let env = self.amplitude_envelope(i, samples);
             
             let source = if voiced {
-                self.glottal_pulse(self.config.pitch_hz)
+                self.voiced_source()
             } else {
                 self.noise() * 0.3
             };
@@ -361,19 +1444,37 @@ impl FormantSynthesizer {
             let f1_out = self.formants[0].process(source);
             let f2_out = self.formants[1].process(source);
             
-            let sample = (f1_out * 0.7 + f2_out * 0.3) * env * self.config.volume * 0.7;
+            let high = self.high_formants_out(source);
+            let sample = (f1_out * 0.7 + f2_out * 0.3 + high) * env * self.config.volume * 0.7;
             output.push(sample);
         }
     }
 
+    /// Synthesize a trill: a voiced approximant whose amplitude dips at
+    /// each tongue tap, `TRILL_HZ` times a second.
+    fn synthesize_trill(&mut self, formants: &FormantValues, samples: usize, output: &mut Vec<f32>) {
+        let start = output.len();
+        self.synthesize_approximant(formants, true, samples, output);
+
+        let sr = self.config.sample_rate as f32;
+        for (i, sample) in output[start..].iter_mut().enumerate() {
+            let phase = 2.0 * PI * TRILL_HZ * i as f32 / sr;
+            *sample *= 1.0 - TRILL_DEPTH * 0.5 * (1.0 - phase.cos());
+        }
+    }
+
     /// Generate an amplitude envelope for natural attack/decay.
     fn amplitude_envelope(&self, sample: usize, total: usize) -> f32 {
+        if !self.config.apply_envelope {
+            return 1.0;
+        }
+
         let attack_len = (total as f32 * 0.1) as usize;
         let decay_len = (total as f32 * 0.15) as usize;
         
-        if sample < attack_len {
+        if sample < attack_len && !self.join_prev {
             sample as f32 / attack_len as f32
-        } else if sample > total - decay_len {
+        } else if sample > total - decay_len && !self.join_next {
             (total - sample) as f32 / decay_len as f32
         } else {
             1.0
@@ -381,25 +1482,331 @@ impl FormantSynthesizer {
     }
 
     /// Synthesize a sequence of phonemes to audio.
+    ///
+    /// Symbols are separated by whitespace, with `_` marking a word gap.
+    /// A trailing `:` (e.g. `t:`) marks a geminate (long) phoneme. Stress
+    /// marks (`'`, `,`, `.`, see [`StressLevel`]) lengthen, raise and
+    /// strengthen the vowels of the syllable that follows them, a `!`
+    /// before a word speaks it louder and higher, a `"` (quoted speech)
+    /// slightly higher, and a `(` (a parenthetical phrase) lower and
+    /// faster. A `?` ends a question, spoken with a final rise, and `|`
+    /// ends any other sentence; pitch falls gently across each sentence.
     pub fn synthesize_phonemes(&mut self, phoneme_str: &str, inventory: &PhonemeInventory) -> Result<Vec<f32>> {
         let mut output = Vec::new();
-        
-        for phoneme_sym in phoneme_str.split_whitespace() {
+        self.synthesize_phonemes_into(phoneme_str, inventory, &mut output)?;
+        Ok(output)
+    }
+
+    /// Synthesize a sequence of phonemes, appending to an existing buffer.
+    pub fn synthesize_phonemes_into(
+        &mut self,
+        phoneme_str: &str,
+        inventory: &PhonemeInventory,
+        output: &mut Vec<f32>,
+    ) -> Result<()> {
+        let symbols: Vec<&str> = phoneme_str.split_whitespace().collect();
+        let contour = sentence_contour(&symbols, inventory);
+        self.synthesize_symbols(&symbols, |_| None, &contour, inventory, output, None);
+        Ok(())
+    }
+
+    /// Synthesize a sequence of phonemes, also returning where each phoneme
+    /// and word gap lies in the audio.
+    pub fn synthesize_phonemes_aligned(
+        &mut self,
+        phoneme_str: &str,
+        inventory: &PhonemeInventory,
+    ) -> Result<(Vec<f32>, Vec<PhonemeTiming>)> {
+        let symbols: Vec<&str> = phoneme_str.split_whitespace().collect();
+        let contour = sentence_contour(&symbols, inventory);
+        let mut output = Vec::new();
+        let mut timings = Vec::new();
+        self.synthesize_symbols(&symbols, |_| None, &contour, inventory, &mut output, Some(&mut timings));
+        Ok((output, timings))
+    }
+
+    /// Synthesize phonemes with an optional pitch (Hz) pinned per phoneme.
+    ///
+    /// Symbols follow the same conventions as
+    /// [`synthesize_phonemes`](Self::synthesize_phonemes); phonemes without a
+    /// pitch use the configured `pitch_hz`.
+    pub fn synthesize_phonemes_with_pitch(
+        &mut self,
+        phonemes: &[(String, Option<f32>)],
+        inventory: &PhonemeInventory,
+    ) -> Result<Vec<f32>> {
+        let symbols: Vec<&str> = phonemes.iter().map(|(symbol, _)| symbol.as_str()).collect();
+        let mut output = Vec::new();
+        let contour = sentence_contour(&symbols, inventory);
+        self.synthesize_symbols(&symbols, |idx| phonemes[idx].1, &contour, inventory, &mut output, None);
+        Ok(output)
+    }
+
+    /// Render phoneme symbols, taking each one's pitch override from
+    /// `pitch_of` and its sentence intonation from `contour` (see
+    /// [`sentence_contour`]), and recording each phoneme's position in
+    /// `output` to `timings` if given.
+    pub(crate) fn synthesize_symbols(
+        &mut self,
+        symbols: &[&str],
+        pitch_of: impl Fn(usize) -> Option<f32>,
+        contour: &[f32],
+        inventory: &PhonemeInventory,
+        output: &mut Vec<f32>,
+        mut timings: Option<&mut Vec<PhonemeTiming>>,
+    ) {
+        let mut stress = StressLevel::Unstressed;
+        let mut emphasis = false;
+        let mut quoted = false;
+        let mut aside = false;
+        // Previous phoneme in the word and its rendered length
+        let mut prev: Option<(&Phoneme, usize)> = None;
+        // Silence rendered by the current run of word gaps
+        let mut pause_run = 0;
+        for (idx, &phoneme_sym) in symbols.iter().enumerate() {
             if phoneme_sym == "_" {
                 // Pause between words
-                let pause_samples = (0.1 * self.config.sample_rate as f32 / self.config.rate) as usize;
-                output.extend(std::iter::repeat_n(0.0, pause_samples));
+                let pause = self.pause_samples(pause_run);
+                if let Some(timings) = timings.as_deref_mut() {
+                    timings.push(PhonemeTiming {
+                        symbol: phoneme_sym.to_string(),
+                        start_sample: output.len(),
+                        end_sample: output.len() + pause,
+                    });
+                }
+                output.extend(std::iter::repeat_n(0.0, pause));
+                pause_run += pause;
+                self.prev_formants = None;
+                self.prev_locus = None;
+                self.last_sample = 0.0;
+                stress = StressLevel::Unstressed;
+                emphasis = false;
+                quoted = false;
+                aside = false;
+                prev = None;
+                continue;
+            }
+            if phoneme_sym == EMPHASIS_MARK {
+                emphasis = true;
+                continue;
+            }
+            if phoneme_sym == QUOTE_MARK {
+                quoted = true;
+                continue;
+            }
+            if phoneme_sym == PARENTHETICAL_MARK {
+                aside = true;
+                continue;
+            }
+            if let Some(level) = StressLevel::from_mark(phoneme_sym) {
+                stress = level;
                 continue;
             }
 
-            if let Some(phoneme) = inventory.get(phoneme_sym) {
-                let duration = (phoneme.duration_ms as f32 / self.config.rate) as u32;
-                let samples = self.synthesize_phoneme(phoneme, duration);
-                output.extend(samples);
+            let (symbol, geminate) = split_length_mark(phoneme_sym);
+            if let Some(phoneme) = inventory.get(symbol) {
+                let next = next_phoneme(&symbols[idx + 1..], inventory);
+                self.aspirate = self.is_aspirated(prev.map(|(p, _)| p), phoneme, next);
+                self.nasalize = self.config.nasalization > 0.0
+                    && matches!(phoneme.category, PhonemeCategory::Vowel | PhonemeCategory::Diphthong)
+                    && next.is_some_and(|n| n.category == PhonemeCategory::Nasal);
+                if self.config.continuous_resonators && is_resonant(phoneme) {
+                    self.join_prev = self.prev_formants.is_some();
+                    self.join_next = next.is_some_and(is_resonant);
+                }
+
+                // Crossfaded voicing transitions replace the attack/decay ramps
+                let crossfade = self.config.voicing_crossfade_ms > 0;
+                self.join_prev |= crossfade && prev.is_some_and(|(p, _)| is_voicing_transition(p, phoneme));
+                self.join_next |= crossfade && next.is_some_and(|n| is_voicing_transition(phoneme, n));
+
+                let (base_pitch, base_volume) = (self.config.pitch_hz, self.config.volume);
+                let mut pitch_factor = contour[idx];
+                if is_stress_bearing(phoneme) {
+                    let (_, pitch, volume) = stress_factors(stress);
+                    pitch_factor *= pitch;
+                    self.config.volume *= volume;
+                }
+                if emphasis {
+                    let scale = self.config.emphasis_scale;
+                    pitch_factor *= 1.0 + (EMPHASIS_PITCH - 1.0) * scale;
+                    self.config.volume *= 1.0 + (EMPHASIS_VOLUME - 1.0) * scale;
+                }
+                if quoted {
+                    pitch_factor *= QUOTE_PITCH;
+                }
+                if aside {
+                    pitch_factor *= PARENTHETICAL_PITCH;
+                }
+                if !self.config.monotone {
+                    let pitch_factor = 1.0 + (pitch_factor - 1.0) * self.config.pitch_range;
+                    self.config.pitch_hz = pitch_of(idx).unwrap_or(base_pitch * pitch_factor);
+                }
+
+                let duration = self.sequence_duration_ms(phoneme, stress, aside);
+                let samples = if geminate {
+                    self.synthesize_geminate(phoneme, duration)
+                } else {
+                    self.synthesize_phoneme(phoneme, duration)
+                };
+
+                let overlap = match prev {
+                    Some((prev_phoneme, prev_len)) => {
+                        self.boundary_crossfade_len(prev_phoneme, phoneme, prev_len, samples.len())
+                    }
+                    None => 0,
+                };
+                let start = output.len() - overlap;
+                for (i, (out, &sample)) in output[start..].iter_mut().zip(&samples).enumerate() {
+                    let t = (i as f32 + 0.5) / overlap as f32 * PI / 2.0;
+                    *out = *out * t.cos() + sample * t.sin();
+                }
+                output.extend(&samples[overlap..]);
+                if let Some(timings) = timings.as_deref_mut() {
+                    timings.push(PhonemeTiming {
+                        symbol: phoneme_sym.to_string(),
+                        start_sample: start,
+                        end_sample: output.len(),
+                    });
+                }
+                prev = Some((phoneme, samples.len()));
+                pause_run = 0;
+
+                self.config.pitch_hz = base_pitch;
+                self.config.volume = base_volume;
+                self.join_prev = false;
+                self.join_next = false;
+                self.aspirate = false;
+                self.nasalize = false;
             }
         }
+    }
 
-        Ok(output)
+    /// Returns true if a voiceless stop between `prev` and `next` (in the
+    /// same word) is aspirated: it starts a syllable, before a vowel, and
+    /// does not follow /s/.
+    fn is_aspirated(&self, prev: Option<&Phoneme>, phoneme: &Phoneme, next: Option<&Phoneme>) -> bool {
+        self.config.aspiration_ms > 0
+            && phoneme.category == PhonemeCategory::Plosive
+            && !phoneme.voiced
+            && next.is_some_and(|n| matches!(n.category, PhonemeCategory::Vowel | PhonemeCategory::Diphthong))
+            && !matches!(prev, Some(p) if p.symbol == "s")
+    }
+
+    /// Samples two adjacent phonemes of the given lengths overlap by, or 0
+    /// if their boundary is not crossfaded.
+    fn boundary_crossfade_len(&self, prev: &Phoneme, next: &Phoneme, prev_len: usize, next_len: usize) -> usize {
+        let crossfade_ms = if self.config.voicing_crossfade_ms > 0 && is_voicing_transition(prev, next) {
+            self.config.voicing_crossfade_ms
+        } else if self.config.continuous_resonators && is_resonant(prev) && is_resonant(next) {
+            self.config.resonant_crossfade_ms
+        } else {
+            0
+        };
+        self.crossfade_len(crossfade_ms, prev_len, next_len)
+    }
+
+    /// Samples in a crossfade of the given length, at most half of either
+    /// phoneme.
+    fn crossfade_len(&self, crossfade_ms: u32, prev_len: usize, next_len: usize) -> usize {
+        let crossfade = crossfade_ms as f32 / 1000.0 * self.config.sample_rate as f32;
+        (crossfade as usize).min(prev_len / 2).min(next_len / 2)
+    }
+
+    /// Estimate the number of samples `synthesize_phonemes` would produce,
+    /// without running the synthesis.
+    pub fn estimate_samples(&self, phoneme_str: &str, inventory: &PhonemeInventory) -> usize {
+        let mut stress = StressLevel::Unstressed;
+        let mut aside = false;
+        let mut prev: Option<(&Phoneme, usize)> = None;
+        let mut total = 0;
+        let mut pause_run = 0;
+        let symbols: Vec<&str> = phoneme_str.split_whitespace().collect();
+        for (idx, &phoneme_sym) in symbols.iter().enumerate() {
+            if phoneme_sym == "_" {
+                let pause = self.pause_samples(pause_run);
+                total += pause;
+                pause_run += pause;
+                stress = StressLevel::Unstressed;
+                aside = false;
+                prev = None;
+                continue;
+            }
+            if phoneme_sym == PARENTHETICAL_MARK {
+                aside = true;
+                continue;
+            }
+            if let Some(level) = StressLevel::from_mark(phoneme_sym) {
+                stress = level;
+                continue;
+            }
+            let (symbol, geminate) = split_length_mark(phoneme_sym);
+            if let Some(phoneme) = inventory.get(symbol) {
+                let samples = self.duration_samples(self.sequence_duration_ms(phoneme, stress, aside));
+                let mut samples = if geminate { samples * 2 } else { samples };
+                let next = next_phoneme(&symbols[idx + 1..], inventory);
+                if self.is_aspirated(prev.map(|(p, _)| p), phoneme, next) {
+                    samples += self.duration_samples(self.config.aspiration_ms);
+                }
+                total += samples;
+                if let Some((prev_phoneme, prev_len)) = prev {
+                    total -= self.boundary_crossfade_len(prev_phoneme, phoneme, prev_len, samples);
+                }
+                prev = Some((phoneme, samples));
+                pause_run = 0;
+            }
+        }
+        total
+    }
+
+    /// Duration of a phoneme in a sequence, scaled by rate and stress, and
+    /// shortened inside a parenthetical phrase (`aside`) (milliseconds).
+    fn sequence_duration_ms(&self, phoneme: &Phoneme, stress: StressLevel, aside: bool) -> u32 {
+        let mut duration = self.scaled_duration_ms(phoneme);
+        if is_stress_bearing(phoneme) {
+            duration = (duration as f32 * stress_factors(stress).0) as u32;
+        }
+        if aside {
+            duration = (duration as f32 * PARENTHETICAL_DURATION) as u32;
+        }
+        duration
+    }
+
+    /// Rate-scaled duration of a phoneme in a sequence (milliseconds).
+    fn scaled_duration_ms(&self, phoneme: &Phoneme) -> u32 {
+        let scaled = (phoneme.duration_ms as f32 / self.config.rate) as u32;
+        if phoneme.category != PhonemeCategory::Fricative {
+            return scaled;
+        }
+        // `duration_samples` divides by the rate again, so the floor is
+        // pre-multiplied to hold the rendered length at `fricative_min_ms`
+        let floor = self.config.fricative_min_ms.min(phoneme.duration_ms) as f32 * self.config.rate;
+        scaled.max(floor as u32)
+    }
+
+    /// Number of samples rendered for a phoneme of the given duration.
+    fn duration_samples(&self, duration_ms: u32) -> usize {
+        let sample_rate = self.config.sample_rate as f32;
+        ((duration_ms as f32 / 1000.0) * sample_rate / self.config.rate) as usize
+    }
+
+    /// Number of samples in a word gap.
+    fn word_gap_samples(&self) -> usize {
+        let pause_secs = self.config.word_gap_ms as f32 / 1000.0;
+        (pause_secs * self.config.sample_rate as f32 / self.config.rate) as usize
+    }
+
+    /// Number of samples the next word gap adds to a run of gaps that has
+    /// already rendered `pause_run` samples, keeping the run within
+    /// `max_pause_ms`.
+    fn pause_samples(&self, pause_run: usize) -> usize {
+        let gap = self.word_gap_samples();
+        if self.config.max_pause_ms == 0 {
+            return gap;
+        }
+        let max_secs = self.config.max_pause_ms as f32 / 1000.0;
+        let max_samples = (max_secs * self.config.sample_rate as f32 / self.config.rate) as usize;
+        gap.min(max_samples.saturating_sub(pause_run))
     }
 
     /// Convert float samples to 16-bit PCM.
@@ -417,6 +1824,7 @@ impl FormantSynthesizer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{band_share, dft_power, f0};
 
     #[test]
     fn test_audio_output_duration() {
@@ -424,6 +1832,62 @@ mod tests {
         assert!((audio.duration_secs() - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_to_bytes_endianness() {
+        let audio = AudioOutput::new(vec![0x1234, -2, 0], 22050, 1);
+
+        let mut manual = Vec::new();
+        for sample in &audio.samples {
+            manual.extend_from_slice(&sample.to_le_bytes());
+        }
+        assert_eq!(audio.to_bytes(Endianness::Little), manual);
+        assert_eq!(audio.to_bytes(Endianness::Big), vec![0x12, 0x34, 0xFF, 0xFE, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_from_f32_full_range() {
+        let audio = AudioOutput::from_f32(&[-1.0, 1.0, 0.0, -2.0, 0.5, -0.5], 22050, 1);
+        assert_eq!(audio.samples, vec![-32768, 32767, 0, -32768, 16384, -16384]);
+
+        // The engine's conversion keeps its old, truncating behaviour
+        let synth = FormantSynthesizer::new(SynthesisConfig::default());
+        assert_eq!(synth.to_pcm16(&[-1.0, 1.0]), vec![-32767, 32767]);
+    }
+
+    #[test]
+    fn test_audio_difference() {
+        let a = AudioOutput::new(vec![0, 1000, -1000, 500], 22050, 1);
+        assert_eq!(a.rms_difference(&a.clone()).unwrap(), 0.0);
+        assert_eq!(a.max_abs_difference(&a.clone()).unwrap(), 0);
+
+        // Shifted by one sample
+        let shifted = AudioOutput::new(vec![500, 0, 1000, -1000], 22050, 1);
+        assert!(a.rms_difference(&shifted).unwrap() > 0.0);
+        assert_eq!(a.max_abs_difference(&shifted).unwrap(), 2000);
+
+        let shorter = AudioOutput::new(vec![0, 1000], 22050, 1);
+        assert!(matches!(a.rms_difference(&shorter), Err(SynthesizerError::AudioError(_))));
+        let other_rate = AudioOutput::new(a.samples.clone(), 16000, 1);
+        assert!(a.max_abs_difference(&other_rate).is_err());
+    }
+
+    #[test]
+    fn test_limiter_tames_transient() {
+        let mut samples = vec![1000i16; 1000];
+        samples.push(30000);
+        samples.push(-28000);
+        samples.extend(vec![1000i16; 5000]);
+        let mut audio = AudioOutput::new(samples, 22050, 1);
+
+        audio.limit(0.5, 10);
+        let ceiling = (0.5 * i16::MAX as f32) as i16;
+        assert!(audio.samples[1000].abs() <= ceiling);
+        assert!(audio.samples[1001].abs() <= ceiling);
+        assert!(audio.samples[..1000].iter().all(|&s| s == 1000));
+        // 10 ms release at 22050 Hz is ~220 samples
+        assert!(audio.samples[1300..].iter().all(|&s| s == 1000));
+    }
+
     #[test]
     fn test_synthesizer_creation() {
         let config = SynthesisConfig::default();
@@ -444,6 +1908,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_noise_generation() {
         let config = SynthesisConfig::default();
         let mut synth = FormantSynthesizer::new(config);
@@ -455,4 +1920,711 @@ mod tests {
         assert!(noise2 >= -1.0 && noise2 <= 1.0);
         assert_ne!(noise1, noise2);
     }
+
+    #[test]
+    fn test_continuous_resonators_smooth_vowel_join() {
+        let inventory = PhonemeInventory::english();
+        let min_frame_rms = |continuous: bool| {
+            let config = SynthesisConfig {
+                continuous_resonators: continuous,
+                ..Default::default()
+            };
+            let mut synth = FormantSynthesizer::new(config);
+            let samples = synth.synthesize_phonemes("i A", &inventory).unwrap();
+
+            // Energy across the vowel-vowel boundary, away from the outer ramps
+            let middle = &samples[samples.len() / 4..samples.len() * 3 / 4];
+            middle
+                .chunks(64)
+                .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt())
+                .fold(f32::MAX, f32::min)
+        };
+
+        assert!(min_frame_rms(true) > min_frame_rms(false) * 2.0);
+    }
+
+    #[test]
+    fn test_word_gap_duration() {
+        let inventory = PhonemeInventory::english();
+        let total_len = |word_gap_ms: u32| {
+            let config = SynthesisConfig {
+                word_gap_ms,
+                ..Default::default()
+            };
+            let mut synth = FormantSynthesizer::new(config);
+            synth.synthesize_phonemes("h E l o _ w 3 r l d _ h i", &inventory).unwrap().len()
+        };
+
+        let default_len = total_len(100);
+        let longer_len = total_len(250);
+        assert_eq!(longer_len - default_len, 2 * (0.15 * SAMPLE_RATE as f32) as usize);
+    }
+
+    #[test]
+    fn test_max_pause_collapses_stacked_gaps() {
+        let inventory = PhonemeInventory::english();
+        let phonemes = "h i _ _ h i";
+        let render = |max_pause_ms: u32| {
+            let config = SynthesisConfig {
+                word_gap_ms: 500,
+                max_pause_ms,
+                ..Default::default()
+            };
+            let mut synth = FormantSynthesizer::new(config);
+            let len = synth.synthesize_phonemes(phonemes, &inventory).unwrap().len();
+            (len, synth.estimate_samples(phonemes, &inventory))
+        };
+
+        let (stacked, _) = render(0);
+        let (capped, estimate) = render(600);
+        assert_eq!(stacked - capped, (0.4 * SAMPLE_RATE as f32) as usize);
+        assert_eq!(capped, estimate);
+        // A cap above the run's total changes nothing
+        assert_eq!(render(2000).0, stacked);
+    }
+
+    #[test]
+    fn test_monotone() {
+        let inventory = PhonemeInventory::english();
+        let symbols = ["'", "A", "_", "!", "A", "_", ".", "A", "_", "A", "?"];
+        let pitch_of = |idx| (idx == 9).then_some(300.0);
+        let contour = sentence_contour(&symbols, &inventory);
+
+        // F0 of each vowel, with the contour, stress, emphasis and a pinned pitch
+        let vowel_f0s = |monotone: bool| -> Vec<f32> {
+            let mut synth = FormantSynthesizer::new(SynthesisConfig { monotone, ..Default::default() });
+            let (mut output, mut timings) = (Vec::new(), Vec::new());
+            synth.synthesize_symbols(&symbols, pitch_of, &contour, &inventory, &mut output, Some(&mut timings));
+            timings
+                .iter()
+                .filter(|t| t.symbol == "A")
+                .map(|t| f0(&output[t.start_sample..t.end_sample]))
+                .collect()
+        };
+
+        let varied = vowel_f0s(false);
+        assert!(varied.iter().any(|&hz| (hz - varied[0]).abs() > 10.0));
+        let flat = vowel_f0s(true);
+        let mean = flat.iter().sum::<f32>() / flat.len() as f32;
+        let std_dev = (flat.iter().map(|hz| (hz - mean).powi(2)).sum::<f32>() / flat.len() as f32).sqrt();
+        assert!(std_dev < 0.5, "{:?}", flat);
+        assert!((mean - 120.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_intonation_profile_pitch_range() {
+        use crate::voice::{IntonationProfile, VoiceConfig};
+
+        let inventory = PhonemeInventory::english();
+        let stress_rise = |profile: IntonationProfile| {
+            let config = SynthesisConfig::from_voice(&VoiceConfig::default().with_intonation(profile));
+            let render = |phonemes: &str| {
+                let mut synth = FormantSynthesizer::new(config.clone());
+                f0(&synth.synthesize_phonemes(phonemes, &inventory).unwrap())
+            };
+            render("' A") - render(". A")
+        };
+
+        let neutral = stress_rise(IntonationProfile::Neutral);
+        assert!(neutral > 5.0);
+        assert!(stress_rise(IntonationProfile::Monotone).abs() < 1.5);
+        assert!(stress_rise(IntonationProfile::Expressive) > neutral * 1.4);
+    }
+
+    #[test]
+    fn test_write_wav_header() {
+        let audio = AudioOutput::new(vec![0, 1, -1, i16::MAX, i16::MIN, 2], 16000, 2);
+        let mut wav = Vec::new();
+        audio.write_wav(&mut wav).unwrap();
+
+        let u16_at = |i: usize| u16::from_le_bytes([wav[i], wav[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes(wav[i..i + 4].try_into().unwrap());
+        assert_eq!(wav.len(), 44 + 12);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32_at(4), 36 + 12);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u16_at(20), 1);
+        assert_eq!(u16_at(22), 2);
+        assert_eq!(u32_at(24), 16000);
+        assert_eq!(u32_at(28), 16000 * 4);
+        assert_eq!(u16_at(32), 4);
+        assert_eq!(u16_at(34), 16);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(40), 12);
+        assert_eq!(&wav[44..], &audio.to_bytes(Endianness::Little)[..]);
+    }
+
+    #[test]
+    fn test_append_concat_and_mix() {
+        let a = AudioOutput::new(vec![100, -100, 30000], 16000, 1);
+        let b = AudioOutput::new(vec![1, 2], 16000, 1);
+
+        let mut joined = a.clone();
+        joined.append(&b).unwrap();
+        assert_eq!(joined.samples, vec![100, -100, 30000, 1, 2]);
+        let concatenated = AudioOutput::concat(&[a.clone(), b.clone(), a.clone()]).unwrap();
+        assert_eq!(concatenated.samples.len(), 8);
+        assert!(AudioOutput::concat(&[]).unwrap().is_empty());
+
+        // Longest length wins, the sum saturates instead of wrapping
+        let loud = AudioOutput::new(vec![0, 0, 10000, 0], 16000, 1);
+        let mixed = a.mix(&loud, 0.5).unwrap();
+        assert_eq!(mixed.samples, vec![100, -100, i16::MAX, 0]);
+
+        let stereo = AudioOutput::new(vec![0, 0], 16000, 2);
+        let other_rate = AudioOutput::new(vec![0], 8000, 1);
+        assert!(joined.append(&stereo).is_err());
+        assert!(AudioOutput::concat(&[a.clone(), other_rate.clone()]).is_err());
+        assert!(a.mix(&other_rate, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_gain_and_normalization() {
+        let mut audio = AudioOutput::new(vec![1000, -2000, 500], SAMPLE_RATE, 1);
+        audio.apply_gain(2.0);
+        assert_eq!(audio.samples, vec![2000, -4000, 1000]);
+        audio.apply_gain(100.0);
+        assert_eq!(audio.samples, vec![i16::MAX, i16::MIN, i16::MAX]);
+
+        // Full-scale negative input does not overflow
+        let mut audio = AudioOutput::new(vec![i16::MIN, 16384], SAMPLE_RATE, 1);
+        audio.normalize_peak(0.5);
+        assert_eq!(audio.samples, vec![-16384, 8192]);
+
+        let mut audio = AudioOutput::new(vec![100, -100, 100, -100], SAMPLE_RATE, 1);
+        audio.normalize_rms(0.1);
+        assert!(audio.samples.iter().all(|s| s.unsigned_abs() == 3277));
+
+        let mut silent = AudioOutput::new(vec![0; 4], SAMPLE_RATE, 1);
+        silent.normalize_peak(1.0);
+        silent.normalize_rms(1.0);
+        assert_eq!(silent.samples, vec![0; 4]);
+        let mut empty = AudioOutput::new(Vec::new(), SAMPLE_RATE, 1);
+        empty.normalize_rms(0.5);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_fades() {
+        let mut audio = AudioOutput::new(vec![1000; 10], 1000, 1);
+        audio.fade_in(4);
+        audio.fade_out(4);
+        assert_eq!(audio.samples, vec![0, 250, 500, 750, 1000, 1000, 750, 500, 250, 0]);
+
+        // Stereo frames fade together, over the whole audio if it is short
+        let mut stereo = AudioOutput::new(vec![1000, -1000, 1000, -1000], 1000, 2);
+        stereo.fade_in(50);
+        assert_eq!(stereo.samples, vec![0, 0, 500, -500]);
+        stereo.fade_out(50);
+        assert_eq!(stereo.samples, vec![0, 0, 0, 0]);
+
+        let mut empty = AudioOutput::new(Vec::new(), 1000, 1);
+        empty.fade_in(10);
+        empty.fade_out(10);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_fade_out_edge_cases() {
+        // A zero-length fade leaves the audio alone
+        let mut audio = AudioOutput::new(vec![1000; 4], 1000, 1);
+        audio.fade_out(0);
+        assert_eq!(audio.samples, vec![1000; 4]);
+
+        // A trailing partial frame is silenced rather than given a negative gain
+        let mut stereo = AudioOutput::new(vec![1000, 1000, 1000, 1000, 1000], 1000, 2);
+        stereo.fade_out(2);
+        assert_eq!(stereo.samples, vec![500, 500, 0, 0, 0]);
+        let mut stereo = AudioOutput::new(vec![1000, 1000, 1000], 1000, 2);
+        stereo.fade_out(0);
+        assert_eq!(stereo.samples, vec![1000, 1000, 1000]);
+    }
+
+    #[test]
+    fn test_trim_silence() {
+        let padding = (0.005 * 1000.0) as usize;
+        let mut samples = vec![0i16; 100];
+        samples.extend([3, 500, -800, 200, 4]);
+        samples.extend(vec![-2i16; 100]);
+        let audio = AudioOutput::new(samples, 1000, 1);
+
+        let trimmed = audio.trimmed(100);
+        assert_eq!(trimmed.samples.len(), 3 + 2 * padding);
+        assert_eq!(trimmed.samples[padding..padding + 3], [500, -800, 200]);
+        assert_eq!(audio.samples.len(), 205);
+
+        // Padding never reaches past the ends of the audio
+        let mut tight = AudioOutput::new(vec![0, 900, 0], 1000, 1);
+        tight.trim_silence(100);
+        assert_eq!(tight.samples, vec![0, 900, 0]);
+
+        // A frame is loud if any of its channels is
+        let mut interleaved = vec![0i16; 40];
+        interleaved.extend([0, 900]);
+        interleaved.extend(vec![0i16; 40]);
+        let stereo = AudioOutput::new(interleaved, 1000, 2);
+        assert_eq!(stereo.trimmed(100).samples.len(), (1 + 2 * padding) * 2);
+
+        assert!(AudioOutput::new(vec![1, -1, 2], 1000, 1).trimmed(100).is_empty());
+    }
+
+    #[test]
+    fn test_resample_length_and_rate() {
+        let ramp: Vec<i16> = (0..2205).map(|i| (i * 10) as i16).collect();
+        let audio = AudioOutput::new(ramp, SAMPLE_RATE, 1);
+
+        for target in [8000, 16000, 44100, 48000] {
+            let resampled = audio.resample(target);
+            assert_eq!(resampled.sample_rate, target);
+            let expected = audio.samples.len() as f64 * target as f64 / SAMPLE_RATE as f64;
+            assert!((resampled.samples.len() as f64 - expected).abs() <= 1.0);
+            assert!((resampled.duration_secs() - audio.duration_secs()).abs() < 1e-3);
+        }
+
+        // A linear ramp stays linear: doubling the rate interpolates midpoints
+        let doubled = audio.resample(SAMPLE_RATE * 2);
+        assert_eq!(&doubled.samples[..5], &[0, 5, 10, 15, 20]);
+
+        // Channels are interpolated separately
+        let stereo = AudioOutput::new(vec![0, 100, 10, 100, 20, 100], 1000, 2);
+        assert_eq!(stereo.resample(2000).samples[..6], [0, 100, 5, 100, 10, 100]);
+
+        let same = audio.resample(SAMPLE_RATE);
+        assert_eq!(same.samples, audio.samples);
+    }
+
+    #[test]
+    fn test_disable_envelope_removes_attack() {
+        let inventory = PhonemeInventory::english();
+        let vowel = inventory.get("A").unwrap();
+        let attack_energy = |apply_envelope: bool| {
+            let config = SynthesisConfig {
+                apply_envelope,
+                ..Default::default()
+            };
+            let mut synth = FormantSynthesizer::new(config);
+            let samples = synth.synthesize_phoneme(vowel, 200);
+            // First 5% of the phoneme lies inside the 10% attack ramp
+            samples[..samples.len() / 20].iter().map(|s| s.abs()).sum::<f32>()
+        };
+
+        assert!(attack_energy(false) > attack_energy(true) * 2.0);
+    }
+
+    #[test]
+    fn test_geminate_consonant_duration() {
+        let inventory = PhonemeInventory::english();
+        let mut synth = FormantSynthesizer::new(SynthesisConfig::default());
+        let single = synth.synthesize_phonemes("t", &inventory).unwrap();
+        // Start from silence so the closure does not fade out the previous burst
+        synth.reset();
+        let long = synth.synthesize_phonemes("t:", &inventory).unwrap();
+        assert_eq!(long.len(), single.len() * 2);
+
+        // The closure is lengthened, the burst keeps its singleton length
+        let closure_len = |samples: &[f32]| samples.iter().take_while(|&&s| s == 0.0).count();
+        assert_eq!(closure_len(&long), closure_len(&single) + single.len());
+
+        let s_single = synth.synthesize_phonemes("s", &inventory).unwrap();
+        let s_long = synth.synthesize_phonemes("s:", &inventory).unwrap();
+        assert_eq!(s_long.len(), s_single.len() * 2);
+    }
+
+    #[test]
+    fn test_fricative_min_duration() {
+        let inventory = PhonemeInventory::english();
+        let render_len = |symbol: &str, rate: f32| {
+            let config = SynthesisConfig { rate, fricative_min_ms: 60, ..Default::default() };
+            let mut synth = FormantSynthesizer::new(config);
+            let len = synth.synthesize_phonemes(symbol, &inventory).unwrap().len();
+            assert_eq!(len, synth.estimate_samples(symbol, &inventory));
+            len as f32
+        };
+
+        let fricative_kept = render_len("s", 3.0) / render_len("s", 1.0);
+        let vowel_kept = render_len("A", 3.0) / render_len("A", 1.0);
+        assert!(fricative_kept > 2.0 * vowel_kept);
+        // 60 ms of a 90 ms /s/
+        assert!((fricative_kept - 0.667).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_plosive_closure_is_click_free() {
+        let inventory = PhonemeInventory::english();
+        // Without the envelope the vowel ends at full amplitude (worst case)
+        let config = SynthesisConfig { apply_envelope: false, ..Default::default() };
+        let mut synth = FormantSynthesizer::new(config);
+        let samples = synth.synthesize_phonemes("A t A", &inventory).unwrap();
+
+        let vowel_len = synth.estimate_samples("A", &inventory);
+        let closure_len = synth.estimate_samples("t", &inventory) * 2 / 3;
+        let delta = |i: usize| (samples[i] - samples[i - 1]).abs();
+        let max_step = |from: usize, to: usize| (from..to).map(delta).fold(0.0, f32::max);
+
+        // Into the closure and into the burst
+        let onset = vowel_len + closure_len;
+        assert!(max_step(vowel_len, vowel_len + 10) < 0.05);
+        assert!(max_step(onset, onset + 3) < 0.05);
+    }
+
+    #[test]
+    fn test_headroom_reduces_clipping() {
+        let inventory = PhonemeInventory::english();
+        let vowel = inventory.get("A").unwrap();
+        let clipping_ratio = |headroom_db: f32| {
+            let config = SynthesisConfig {
+                volume: 4.0,
+                headroom_db,
+                ..Default::default()
+            };
+            let mut synth = FormantSynthesizer::new(config);
+            let samples = synth.synthesize_phoneme(vowel, 200);
+            samples.iter().filter(|s| s.abs() > 1.0).count() as f32 / samples.len() as f32
+        };
+
+        let overdriven = clipping_ratio(0.0);
+        assert!(overdriven > 0.0);
+        assert!(clipping_ratio(-12.0) < overdriven);
+    }
+
+    #[test]
+    fn test_config_from_voice() {
+        use crate::voice::{Language, VoiceVariant};
+
+        let voice = VoiceConfig::new(Language::Spanish)
+            .with_variant(VoiceVariant::Female2)
+            .with_rate(350)
+            .with_volume(150);
+        let config = SynthesisConfig::from_voice(&voice);
+        assert_eq!(config.pitch_hz, voice.effective_pitch_hz());
+        assert_eq!(config.rate, 2.0);
+        assert_eq!(config.volume, 1.0);
+        assert_eq!(config.sample_rate, SAMPLE_RATE);
+        assert_eq!(config.aspiration_ms, 0);
+        assert!(SynthesisConfig::from_voice(&VoiceConfig::default()).aspiration_ms > 0);
+    }
+
+    #[test]
+    fn test_onset_stops_are_aspirated() {
+        let inventory = PhonemeInventory::english();
+        let render = |phonemes: &str, aspiration_ms: u32| {
+            let mut synth = FormantSynthesizer::new(SynthesisConfig { aspiration_ms, ..Default::default() });
+            let samples = synth.synthesize_phonemes(phonemes, &inventory).unwrap();
+            assert_eq!(samples.len(), synth.estimate_samples(phonemes, &inventory));
+            samples
+        };
+
+        // "pin" gains 40 ms of noise between burst and vowel, "spin" does not
+        let extra = render("p I n", 40).len() - render("p I n", 0).len();
+        assert_eq!(extra, (0.04 * SAMPLE_RATE as f32) as usize);
+        assert_eq!(render("s p I n", 40).len(), render("s p I n", 0).len());
+
+        // The aspiration is audible noise
+        let stop = inventory.get("p").unwrap();
+        let stop_len = FormantSynthesizer::new(SynthesisConfig::default()).duration_samples(stop.duration_ms);
+        let aspiration = &render("p I n", 40)[stop_len..stop_len + extra];
+        let rms = (aspiration.iter().map(|s| s * s).sum::<f32>() / extra as f32).sqrt();
+        assert!(rms > 0.05, "aspiration rms {}", rms);
+    }
+
+    #[test]
+    fn test_vowel_before_nasal_is_nasalized() {
+        let inventory = PhonemeInventory::english();
+        let vowel_len = FormantSynthesizer::new(SynthesisConfig::default())
+            .duration_samples(inventory.get("&").unwrap().duration_ms);
+        let render = |nasalization: f32| {
+            let mut synth = FormantSynthesizer::new(SynthesisConfig { nasalization, ..Default::default() });
+            synth.synthesize_phonemes("& n", &inventory).unwrap()
+        };
+        let (oral, nasal) = (render(0.0), render(0.6));
+
+        // The start of the vowel is untouched; towards its end the widened,
+        // partly nasal F1 loses prominence
+        let half = vowel_len / 2;
+        assert_eq!(oral[..half], nasal[..half]);
+        let f1 = |samples: &[f32]| band_share(&samples[vowel_len * 3 / 4..vowel_len], 600..750);
+        assert!(f1(&nasal) < 0.6 * f1(&oral));
+    }
+
+    #[test]
+    fn test_high_formants() {
+        let inventory = PhonemeInventory::english();
+        let render = |config: SynthesisConfig| {
+            let mut synth = FormantSynthesizer::new(config);
+            synth.synthesize_phonemes("A", &inventory).unwrap()
+        };
+        let f4_band = |samples: &[f32]| band_share(samples, 3300..3700);
+
+        let plain = render(SynthesisConfig { f4_hz: 0.0, f5_hz: 0.0, ..Default::default() });
+        let with_f4 = render(SynthesisConfig::default());
+        assert!(f4_band(&with_f4) > 2.0 * f4_band(&plain));
+
+        // Higher voices get a shorter vocal tract
+        let male = SynthesisConfig::male();
+        let female = SynthesisConfig::female();
+        assert_eq!(male.f4_hz, 3500.0);
+        assert!(female.f4_hz > male.f4_hz && female.f5_hz > male.f5_hz);
+
+        // Formants above the usable range are dropped rather than aliased
+        let synth = FormantSynthesizer::new(SynthesisConfig { sample_rate: 8000, ..Default::default() });
+        assert!(synth.high_formants[0].1 > 0.0 && synth.high_formants[1].1 == 0.0);
+    }
+
+    #[test]
+    fn test_formant_scale() {
+        use crate::voice::{Language, VoiceVariant};
+
+        // Strongest frequency in the F2 region of /i/ (F2 = 2290 Hz)
+        let inventory = PhonemeInventory::english();
+        let f2_peak = |variant: VoiceVariant| {
+            let voice = VoiceConfig::new(Language::English).with_variant(variant);
+            let config = SynthesisConfig { pitch_hz: 100.0, ..SynthesisConfig::from_voice(&voice) };
+            let mut synth = FormantSynthesizer::new(config);
+            let samples = synth.synthesize_phonemes("i", &inventory).unwrap();
+            (1800..3200)
+                .step_by(10)
+                .max_by(|&a, &b| band_share(&samples, a..a + 1).total_cmp(&band_share(&samples, b..b + 1)))
+                .unwrap() as f32
+        };
+
+        let male = f2_peak(VoiceVariant::Male1);
+        assert!(f2_peak(VoiceVariant::Female1) > 1.1 * male);
+        assert!(f2_peak(VoiceVariant::Child) > f2_peak(VoiceVariant::Female1));
+    }
+
+    #[test]
+    fn test_trill_flutters() {
+        let inventory = PhonemeInventory::spanish();
+        let config = SynthesisConfig::default();
+        let frame = (config.sample_rate as f32 / config.pitch_hz) as usize;
+        let frame_rate = config.sample_rate as f32 / frame as f32;
+
+        // Strongest modulation frequency (15-60 Hz) of the RMS envelope,
+        // one frame per pitch period
+        let flutter_hz = |symbol: &str| {
+            let mut synth = FormantSynthesizer::new(config.clone());
+            let samples = synth.synthesize_phoneme(inventory.get(symbol).unwrap(), 500);
+            let envelope: Vec<f32> = samples
+                .chunks_exact(frame)
+                .map(|c| (c.iter().map(|s| s * s).sum::<f32>() / frame as f32).sqrt())
+                .collect();
+            let mean = envelope.iter().sum::<f32>() / envelope.len() as f32;
+            let centered: Vec<f32> = envelope.iter().map(|e| e - mean).collect();
+            let power = |hz: f32| dft_power(&centered, hz, frame_rate);
+            let peak = (15..=60).max_by(|&a, &b| power(a as f32).total_cmp(&power(b as f32))).unwrap();
+            (peak, power(peak as f32))
+        };
+
+        let (trill_hz, trill_power) = flutter_hz("rr");
+        assert!((25..=30).contains(&trill_hz), "trill modulated at {} Hz", trill_hz);
+        let (_, tap_power) = flutter_hz("r");
+        assert!(trill_power > 10.0 * tap_power);
+    }
+
+    #[test]
+    fn test_diphthong_glide_point_shifts_transition() {
+        let inventory = PhonemeInventory::english();
+        let diphthong = inventory.get("aI").unwrap();
+
+        // Energy share between 1.6 and 2.2 kHz (near the final F2 target)
+        // in the middle fifth of the diphthong
+        let mid_f2_share = |glide_point: f32| {
+            let config = SynthesisConfig { diphthong_glide_point: glide_point, ..Default::default() };
+            let samples = FormantSynthesizer::new(config).synthesize_phoneme(diphthong, 400);
+            band_share(&samples[samples.len() * 2 / 5..samples.len() * 3 / 5], 1600..2200)
+        };
+
+        // An early glide point reaches the high F2 target sooner
+        let (early, even, late) = (mid_f2_share(0.2), mid_f2_share(0.5), mid_f2_share(0.8));
+        assert!(early > 1.5 * even);
+        assert!(even > 1.5 * late);
+    }
+
+    #[test]
+    fn test_breathiness_adds_aperiodic_energy() {
+        let inventory = PhonemeInventory::english();
+        let vowel = inventory.get("A").unwrap();
+        let render = |breathiness: f32| {
+            let config = SynthesisConfig { breathiness, ..Default::default() };
+            FormantSynthesizer::new(config).synthesize_phoneme(vowel, 300)
+        };
+
+        let clean = render(0.0);
+        let breathy = render(0.3);
+        let high = |samples: &[f32]| band_share(samples, 4000..6000);
+        assert!(high(&breathy) > 10.0 * high(&clean));
+        // The F1 peak (730 Hz) is kept and still dominates
+        let f1 = |samples: &[f32]| band_share(samples, 600..900);
+        assert!(f1(&breathy) > 0.5 * f1(&clean));
+        assert!(f1(&breathy) > 10.0 * high(&breathy));
+    }
+
+    #[test]
+    fn test_jitter_and_shimmer() {
+        let inventory = PhonemeInventory::english();
+        let vowel = inventory.get("A").unwrap();
+        let render = |config: SynthesisConfig| FormantSynthesizer::new(config).synthesize_phoneme(vowel, 300);
+
+        // Perturbations follow the seed, so a seed reproduces them exactly
+        let seeded = |seed| render(SynthesisConfig::default().with_seed(seed));
+        assert_eq!(seeded(1), seeded(1));
+        assert_ne!(seeded(1), seeded(2));
+
+        // Without them the voice source is strictly periodic and ignores the seed
+        let periodic = |seed| render(SynthesisConfig { jitter: 0.0, shimmer: 0.0, ..Default::default() }.with_seed(seed));
+        assert_eq!(periodic(1), periodic(2));
+
+        // Sample for sample, the source is the one with perturbation
+        // bypassed: each cycle's pitch and amplitude factors undone as soon
+        // as they are drawn
+        let source = |config: SynthesisConfig, bypass: bool| {
+            let mut synth = FormantSynthesizer::new(config);
+            (0..SAMPLE_RATE / 10)
+                .map(|_| {
+                    synth.cycle_pitch = if bypass { 1.0 } else { synth.cycle_pitch };
+                    let sample = synth.glottal_pulse(120.0);
+                    if bypass { sample / synth.cycle_amplitude } else { sample }
+                })
+                .collect::<Vec<f32>>()
+        };
+        let unperturbed = source(SynthesisConfig::default(), true);
+        let same = |a: &[f32], b: &[f32]| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-6);
+        assert!(same(&source(SynthesisConfig { jitter: 0.0, shimmer: 0.0, ..Default::default() }, false), &unperturbed));
+        assert!(!same(&source(SynthesisConfig::default(), false), &unperturbed));
+    }
+
+    #[test]
+    fn test_whisper_has_no_pitch() {
+        let inventory = PhonemeInventory::english();
+        let vowel = inventory.get("A").unwrap();
+        let render = |whisper: bool| {
+            let config = SynthesisConfig { whisper, jitter: 0.0, ..Default::default() };
+            FormantSynthesizer::new(config).synthesize_phoneme(vowel, 300)
+        };
+
+        // Normalized autocorrelation at one pitch period
+        let periodicity = |samples: &[f32]| {
+            let lag = (SAMPLE_RATE as f32 / 120.0).round() as usize;
+            let energy: f32 = samples.iter().map(|s| s * s).sum();
+            let corr: f32 = samples.iter().zip(&samples[lag..]).map(|(a, b)| a * b).sum();
+            corr / energy
+        };
+        let (spoken, whispered) = (render(false), render(true));
+        assert_eq!(spoken.len(), whispered.len());
+        assert!(periodicity(&spoken) > 0.5);
+        assert!(periodicity(&whispered) < 0.2);
+        assert!(whispered.iter().any(|s| s.abs() > 0.1));
+    }
+
+    #[test]
+    fn test_spectral_tilt() {
+        let inventory = PhonemeInventory::english();
+        let vowel = inventory.get("A").unwrap();
+        let render = |spectral_tilt: f32| {
+            let config = SynthesisConfig { spectral_tilt, ..Default::default() };
+            FormantSynthesizer::new(config).synthesize_phoneme(vowel, 300)
+        };
+
+        let high = |tilt: f32| band_share(&render(tilt), 2000..3000);
+        let neutral = high(0.0);
+        assert!(high(0.7) < 0.5 * neutral);
+        assert!(high(-0.7) > 1.5 * neutral);
+    }
+
+    #[test]
+    fn test_stress_lengthens_vowels() {
+        let inventory = PhonemeInventory::english();
+        let synth = FormantSynthesizer::new(SynthesisConfig::default());
+        let plain = synth.estimate_samples("b A", &inventory);
+        let secondary = synth.estimate_samples(", b A", &inventory);
+        let primary = synth.estimate_samples("' b A", &inventory);
+        assert!(plain < secondary && secondary < primary);
+
+        // Marks only shape the following syllable and match the rendering
+        let mut synth = FormantSynthesizer::new(SynthesisConfig::default());
+        let marked = "' b A . b A";
+        let samples = synth.synthesize_phonemes(marked, &inventory).unwrap();
+        assert_eq!(samples.len(), synth.estimate_samples(marked, &inventory));
+        assert_eq!(samples.len(), primary + plain);
+
+        // Stressed vowels are also louder
+        let energy = |phonemes: &str| {
+            let mut synth = FormantSynthesizer::new(SynthesisConfig::default());
+            let samples = synth.synthesize_phonemes(phonemes, &inventory).unwrap();
+            samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32
+        };
+        assert!(energy("' A") > 1.15 * energy("A"));
+    }
+
+    #[test]
+    fn test_voicing_transition_is_tapered() {
+        let inventory = PhonemeInventory::english();
+        // RMS over one pitch period, in half-period steps across the vowel-/s/ seam
+        let seam_energy = |voicing_crossfade_ms: u32| {
+            let config = SynthesisConfig { voicing_crossfade_ms, ..Default::default() };
+            let period = (config.sample_rate as f32 / config.pitch_hz) as usize;
+            let mut synth = FormantSynthesizer::new(config);
+            let samples = synth.synthesize_phonemes("A s", &inventory).unwrap();
+            assert_eq!(samples.len(), synth.estimate_samples("A s", &inventory));
+
+            let vowel_len = synth.estimate_samples("A", &inventory);
+            let seam = vowel_len - synth.crossfade_len(voicing_crossfade_ms, vowel_len, usize::MAX) / 2;
+            (0..12)
+                .map(|k| {
+                    let start = seam - 3 * period + k * period / 2;
+                    let energy: f32 = samples[start..start + period].iter().map(|s| s * s).sum();
+                    (energy / period as f32).sqrt()
+                })
+                .collect::<Vec<f32>>()
+        };
+
+        // Energy falls steadily from the vowel to the fricative level...
+        let tapered = seam_energy(10);
+        assert!(tapered.windows(2).all(|w| w[1] <= w[0] * 1.05));
+        // ...instead of dipping below the fricative level at the seam
+        let abrupt = seam_energy(0);
+        let fricative_level = *abrupt.last().unwrap();
+        assert!(abrupt.iter().any(|&e| e < 0.8 * fricative_level));
+    }
+
+    #[test]
+    fn test_resonant_joins_do_not_click() {
+        let inventory = PhonemeInventory::english();
+        let max_delta = |config: SynthesisConfig| {
+            let mut synth = FormantSynthesizer::new(config);
+            let samples = synth.synthesize_phonemes("h E l o", &inventory).unwrap();
+            assert_eq!(samples.len(), synth.estimate_samples("h E l o", &inventory));
+            samples.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max)
+        };
+
+        // With the defaults the largest steps are the /h/ noise, not the
+        // voiced joins
+        assert!(max_delta(SynthesisConfig::default()) < 0.4);
+        assert!(max_delta(SynthesisConfig { resonant_crossfade_ms: 0, ..Default::default() }) > 0.5);
+    }
+
+    #[test]
+    fn test_coarticulation_locus_onset() {
+        let inventory = PhonemeInventory::english();
+        let vowel = inventory.get("i").unwrap().formants.unwrap();
+        let onset_after = |consonant: &str, config: SynthesisConfig| {
+            let mut synth = FormantSynthesizer::new(config);
+            synth.synthesize_phoneme(inventory.get(consonant).unwrap(), 80);
+            synth.locus_onset(&vowel, usize::MAX)
+        };
+
+        let (labial, len) = onset_after("b", SynthesisConfig::default()).unwrap();
+        let (velar, _) = onset_after("g", SynthesisConfig::default()).unwrap();
+        assert!(labial.f2 < velar.f2);
+        assert_eq!(labial.f1, vowel.f1);
+        assert_eq!(len, (0.03 * SAMPLE_RATE as f32) as usize);
+
+        // The transition shortens with faster speech
+        let fast = SynthesisConfig { rate: 2.0, ..Default::default() };
+        assert_eq!(onset_after("b", fast).unwrap().1, len / 2);
+
+        let disabled = SynthesisConfig { coarticulation: false, ..Default::default() };
+        assert!(onset_after("b", disabled).is_none());
+        assert!(onset_after("A", SynthesisConfig::default()).is_none());
+    }
 }
@@ -1,10 +1,13 @@
-//! Text-to-phoneme conversion for English and Spanish.
+//! Text-to-phoneme conversion for English, Spanish and French.
 //!
 //! This module implements grapheme-to-phoneme (G2P) conversion rules
 //! for converting text to phoneme sequences.
 
 use crate::error::{Result, SynthesizerError};
-use crate::phoneme::PhonemeInventory;
+use crate::phoneme::{
+    PhonemeCategory, PhonemeInventory, StressLevel, EMPHASIS_MARK, PARENTHETICAL_MARK, QUESTION_MARK, QUOTE_MARK,
+    STATEMENT_MARK,
+};
 use std::collections::HashMap;
 
 /// Grapheme-to-phoneme converter.
@@ -17,6 +20,121 @@ pub struct G2PConverter {
     rules: HashMap<String, Vec<G2PRule>>,
     /// Exception dictionary.
     exceptions: HashMap<String, String>,
+    /// All-caps words spoken as words rather than spelled, with optional
+    /// explicit phonemes (`None` = use the normal rules).
+    spoken_initialisms: HashMap<String, Option<String>>,
+    /// Word gaps (`_`) emitted at a single line break.
+    line_break_pause: usize,
+    /// Word gaps (`_`) emitted between paragraphs (blank-line separated).
+    paragraph_pause: usize,
+    /// Fail conversion when a character has no pronunciation rule.
+    strict: bool,
+    /// How all-caps words are read.
+    caps_policy: CapsPolicy,
+    /// Mark words inside quotation marks for a shifted pitch.
+    quote_detection: bool,
+    /// Mark parenthetical phrases for a lowered pitch, a faster rate and
+    /// bracketing pauses.
+    parenthetical_detection: bool,
+    /// How letters without pronunciation rules are read.
+    foreign_text_policy: ForeignTextPolicy,
+    /// Reduce full vowels in unstressed English syllables to schwa.
+    vowel_reduction: bool,
+    /// Flap /t/ and /d/ between vowels (American English).
+    flapping: bool,
+}
+
+/// Word gaps (`_`) standing in for a run of foreign text under
+/// `ForeignTextPolicy::Pause`.
+const FOREIGN_TEXT_PAUSE: usize = 3;
+
+/// Word gaps (`_`) before and after a parenthetical phrase.
+const PARENTHETICAL_PAUSE: usize = 2;
+
+/// Longest run of words between two commas read as a parenthetical
+/// phrase (", you know,").
+const PARENTHETICAL_MAX_WORDS: usize = 3;
+
+/// Coordinating conjunctions, which join a clause or list item rather than
+/// open a parenthetical phrase ("and then," "and plums").
+const CONJUNCTIONS: &[&str] = &["and", "or", "but", "nor", "so", "yet", "y", "e", "o", "u", "pero", "ni", "et", "ou", "mais"];
+
+/// Largest number read out as words; longer digit strings are left as
+/// they are.
+const MAX_SPOKEN_NUMBER: u64 = 999_999_999_999;
+
+/// How letters the language has no rules for are read, such as a Japanese
+/// name in an English sentence or "é" in English text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default)]
+pub enum ForeignTextPolicy {
+    /// Drop them (they are still listed by
+    /// [`G2PConverter::convert_verbose`]).
+    #[default]
+    Skip,
+    /// Replace each run of them with a pause, so the omission is audible.
+    Pause,
+    /// Read accented Latin letters as their base letter ("é" as "e");
+    /// other scripts are replaced with a pause.
+    Transliterate,
+}
+
+/// How all-caps words ("HTTP", "STOP") are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default)]
+pub enum CapsPolicy {
+    /// Spell them out as initialisms, unless registered as spoken words.
+    #[default]
+    Acronym,
+    /// Read pronounceable ones as emphasized (shouted) words; spell out the
+    /// rest ("STOP" is shouted, "HTTP" is spelled).
+    Shout,
+    /// Read them like lowercase words.
+    Ignore,
+}
+
+/// Regional pronunciation of Spanish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default)]
+pub enum SpanishVariant {
+    /// Peninsular Spanish: "c" before e/i and "z" are /θ/, "ll" is /ʎ/.
+    #[default]
+    Castilian,
+    /// Seseo and yeísmo: "c" before e/i and "z" are /s/, "ll" is /j/.
+    LatinAmerican,
+}
+
+/// Result of [`G2PConverter::convert_verbose`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionReport {
+    /// The phoneme sequence, as returned by [`G2PConverter::convert`].
+    pub phonemes: String,
+    /// Letters with no pronunciation rule that were skipped, and digits
+    /// that could not be read as a number, in order of first appearance.
+    /// Punctuation, apostrophes and hyphens are not reported.
+    pub dropped: Vec<char>,
+}
+
+/// Options for rendering IPA transcriptions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpaOptions {
+    /// Separate syllables with `.`.
+    pub syllable_marks: bool,
+    /// Mark primary stress in polysyllabic words with `ˈ` and secondary
+    /// stress with `ˌ`.
+    pub stress_marks: bool,
+    /// Character placed between words.
+    pub word_separator: char,
+}
+
+impl Default for IpaOptions {
+    fn default() -> Self {
+        Self {
+            syllable_marks: false,
+            stress_marks: false,
+            word_separator: ' ',
+        }
+    }
 }
 
 /// A grapheme-to-phoneme conversion rule.
@@ -24,55 +142,328 @@ pub struct G2PConverter {
 struct G2PRule {
     /// Pattern to match (graphemes).
     pattern: String,
-    /// Left context (regex-like pattern, empty = any).
-    left_context: String,
-    /// Right context (regex-like pattern, empty = any).
-    right_context: String,
+    /// Context the letters before the pattern must match.
+    left_context: RuleContext,
+    /// Context the letters after the pattern must match.
+    right_context: RuleContext,
     /// Resulting phonemes.
     phonemes: String,
     /// Priority (higher = applied first).
     priority: i32,
 }
 
+/// A left or right context of a G2P rule.
+///
+/// Contexts are written as a small regex-like pattern, read outward from
+/// the rule's letters:
+///
+/// - a letter matches itself
+/// - `[...]` matches any listed letter, `[^...]` any other letter
+/// - `V` matches any vowel letter (accented or not) and `C` any other
+///   letter; both also work inside `[...]`
+/// - `#`, `^` and `$` match the word boundary
+/// - `+` after an item matches it one or more times, `?` zero or one time
+/// - `|` separates alternatives
+///
+/// An empty context matches anything. For example `C?e$` after "a" matches
+/// "make", and `^[^aeiou]+` before "y" matches "fly".
+#[derive(Debug, Clone)]
+struct RuleContext {
+    /// Alternatives, each a sequence of items ordered outward from the rule.
+    alternatives: Vec<Vec<ContextItem>>,
+}
+
+/// One item of a rule context.
+#[derive(Debug, Clone)]
+struct ContextItem {
+    /// The letter set or word boundary to match.
+    atom: ContextAtom,
+    /// How many consecutive letters the atom matches (ignored for a
+    /// boundary).
+    repeat: Repeat,
+}
+
+/// How many times a context item matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Repeat {
+    Once,
+    /// One or more times (`+`).
+    OneOrMore,
+    /// Zero or one time (`?`).
+    Optional,
+}
+
+/// What a single context item matches.
+#[derive(Debug, Clone)]
+enum ContextAtom {
+    /// The start or end of the word.
+    Boundary,
+    /// A letter in (or, negated, not in) a set.
+    Class { negated: bool, members: Vec<ClassMember> },
+}
+
+/// A member of a letter set.
+#[derive(Debug, Clone, Copy)]
+enum ClassMember {
+    Letter(char),
+    Vowel,
+    Consonant,
+}
+
+impl ClassMember {
+    fn matches(self, c: char) -> bool {
+        match self {
+            ClassMember::Letter(letter) => c == letter,
+            ClassMember::Vowel => is_vowel_letter(c),
+            ClassMember::Consonant => c.is_alphabetic() && !is_vowel_letter(c),
+        }
+    }
+}
+
+impl RuleContext {
+    /// Parses a context pattern. Items of a left context are reversed so
+    /// both sides are matched outward from the rule.
+    fn parse(pattern: &str, left: bool) -> Self {
+        let alternatives = pattern
+            .split('|')
+            .map(|alternative| {
+                let mut items: Vec<ContextItem> = Vec::new();
+                let mut chars = alternative.chars();
+                while let Some(c) = chars.next() {
+                    let atom = match c {
+                        '#' | '^' | '$' => ContextAtom::Boundary,
+                        '+' | '?' => {
+                            if let Some(last) = items.last_mut() {
+                                last.repeat = if c == '+' { Repeat::OneOrMore } else { Repeat::Optional };
+                            }
+                            continue;
+                        }
+                        '[' => {
+                            let mut class: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                            let negated = class.starts_with('^');
+                            if negated {
+                                class.remove(0);
+                            }
+                            ContextAtom::Class { negated, members: class.chars().map(class_member).collect() }
+                        }
+                        c => ContextAtom::Class { negated: false, members: vec![class_member(c)] },
+                    };
+                    items.push(ContextItem { atom, repeat: Repeat::Once });
+                }
+                if left {
+                    items.reverse();
+                }
+                items
+            })
+            .collect();
+        Self { alternatives }
+    }
+
+    /// Returns true if the letters, in order outward from the rule, match
+    /// any alternative.
+    fn matches(&self, letters: &[char]) -> bool {
+        self.alternatives.iter().any(|items| match_items(items, letters))
+    }
+}
+
+/// Reads a context letter as a set member (`V` and `C` are letter classes).
+fn class_member(c: char) -> ClassMember {
+    match c {
+        'V' => ClassMember::Vowel,
+        'C' => ClassMember::Consonant,
+        c => ClassMember::Letter(c),
+    }
+}
+
+/// Matches context items against letters, backtracking over repeats.
+/// Quantifiers on a word boundary are ignored.
+fn match_items(items: &[ContextItem], letters: &[char]) -> bool {
+    let Some((item, rest)) = items.split_first() else {
+        return true;
+    };
+    match &item.atom {
+        ContextAtom::Boundary => letters.is_empty() && match_items(rest, letters),
+        ContextAtom::Class { negated, members } => {
+            let in_class = |c: &char| members.iter().any(|m| m.matches(*c)) != *negated;
+            let run = letters.iter().take_while(|c| in_class(c)).count();
+            match item.repeat {
+                Repeat::Once => run > 0 && match_items(rest, &letters[1..]),
+                Repeat::OneOrMore => (1..=run).rev().any(|n| match_items(rest, &letters[n..])),
+                Repeat::Optional => (run > 0 && match_items(rest, &letters[1..])) || match_items(rest, letters),
+            }
+        }
+    }
+}
+
 impl G2PConverter {
-    /// Creates a new G2P converter for English.
-    pub fn english() -> Self {
-        let mut converter = Self {
-            language: "en".to_string(),
-            inventory: PhonemeInventory::english(),
+    /// A converter with no rules and default settings.
+    fn base(language: &str, inventory: PhonemeInventory) -> Self {
+        Self {
+            language: language.to_string(),
+            inventory,
             rules: HashMap::new(),
             exceptions: HashMap::new(),
-        };
+            spoken_initialisms: HashMap::new(),
+            line_break_pause: 2,
+            paragraph_pause: 5,
+            strict: false,
+            caps_policy: CapsPolicy::Acronym,
+            quote_detection: false,
+            parenthetical_detection: false,
+            foreign_text_policy: ForeignTextPolicy::Skip,
+            vowel_reduction: false,
+            flapping: false,
+        }
+    }
+
+    /// Creates a new G2P converter for English.
+    pub fn english() -> Self {
+        let mut converter = Self::base("en", PhonemeInventory::english());
+        converter.vowel_reduction = true;
+        converter.flapping = true;
         converter.load_english_rules();
         converter.load_english_exceptions();
+        converter.load_english_initialisms();
         converter
     }
 
-    /// Creates a new G2P converter for Spanish.
+    /// Creates a new G2P converter for Castilian Spanish.
     pub fn spanish() -> Self {
-        let mut converter = Self {
-            language: "es".to_string(),
-            inventory: PhonemeInventory::spanish(),
-            rules: HashMap::new(),
-            exceptions: HashMap::new(),
-        };
+        Self::spanish_with_variant(SpanishVariant::Castilian)
+    }
+
+    /// Creates a new G2P converter for the given variety of Spanish.
+    pub fn spanish_with_variant(variant: SpanishVariant) -> Self {
+        let mut converter = Self::base("es", PhonemeInventory::spanish());
         converter.load_spanish_rules();
+        converter.set_spanish_variant(variant);
+        converter.load_spanish_initialisms();
+        converter
+    }
+
+    /// Creates a new G2P converter for French.
+    pub fn french() -> Self {
+        let mut converter = Self::base("fr", PhonemeInventory::french());
+        converter.load_french_rules();
+        converter.load_french_exceptions();
+        converter.load_french_initialisms();
         converter
     }
 
+    /// Sets the pauses used for line breaks and paragraphs in plain text.
+    ///
+    /// Both are measured in word gaps (`_`); a plain space is one gap.
+    #[must_use]
+    pub fn with_break_pauses(mut self, line_break: usize, paragraph: usize) -> Self {
+        self.line_break_pause = line_break.max(1);
+        self.paragraph_pause = paragraph.max(1);
+        self
+    }
+
+    /// Sets strict mode.
+    ///
+    /// By default letters without a pronunciation rule (e.g. Cyrillic text
+    /// under English) and digits that are not part of a number ("x2") are
+    /// skipped silently; in strict mode conversion fails with a
+    /// `PhonemeError` naming them instead. Use
+    /// [`convert_verbose`](Self::convert_verbose) to see what is skipped
+    /// without failing.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Sets how all-caps words are read.
+    ///
+    /// Capitals are ambiguous: "NASA" is an acronym, "STOP" is usually
+    /// shouting. Under `CapsPolicy::Shout`, marked output
+    /// ([`convert_marked`](Self::convert_marked)) puts a `!` token before
+    /// shouted words, which the synthesizer speaks louder and higher.
+    pub fn set_caps_policy(&mut self, policy: CapsPolicy) {
+        self.caps_policy = policy;
+    }
+
+    /// Sets whether quoted speech is marked for the synthesizer.
+    ///
+    /// When on, marked output ([`convert_marked`](Self::convert_marked))
+    /// puts a `"` token before each word between a matched pair of
+    /// straight, curly or angle quotation marks on one line, which the
+    /// synthesizer speaks at a slightly raised pitch to set it off from
+    /// the narration. Off by default.
+    pub fn set_quote_detection(&mut self, detect: bool) {
+        self.quote_detection = detect;
+    }
+
+    /// Sets whether parenthetical phrases are marked for the synthesizer.
+    ///
+    /// When on, marked output ([`convert_marked`](Self::convert_marked))
+    /// puts a `(` token before each word of a parenthetical phrase and
+    /// brackets the phrase with short pauses; the synthesizer speaks it
+    /// lower and faster than the main clause. A phrase is a matched pair
+    /// of parentheses on one line (nested ones are read as part of the
+    /// outermost), or up to three words between two commas in a sentence
+    /// (", you know,"). Short list items between commas match too, which
+    /// is why this is off by default.
+    pub fn set_parenthetical_detection(&mut self, detect: bool) {
+        self.parenthetical_detection = detect;
+    }
+
+    /// Sets how letters without pronunciation rules are read.
+    pub fn set_foreign_text_policy(&mut self, policy: ForeignTextPolicy) {
+        self.foreign_text_policy = policy;
+    }
+
+    /// Sets whether full vowels in unstressed English syllables are reduced
+    /// to schwa ("fantastic" -> `f @ n t & s t I k`). On by default; turn it
+    /// off for clearer citation-form speech. Other languages do not reduce
+    /// vowels.
+    pub fn set_vowel_reduction(&mut self, reduce: bool) {
+        self.vowel_reduction = reduce;
+    }
+
+    /// Sets whether English /t/ and /d/ before an unstressed vowel become a
+    /// flap (`4`, IPA ɾ), as in American "butter" and "city". On by
+    /// default; turn it off for a British accent.
+    pub fn set_flapping(&mut self, flapping: bool) {
+        self.flapping = flapping;
+    }
+
+    /// Switches a Spanish converter between Castilian and Latin American
+    /// pronunciation of "c", "z" and "ll". Exceptions are kept; converters
+    /// for other languages are unchanged.
+    pub fn set_spanish_variant(&mut self, variant: SpanishVariant) {
+        if self.language != "es" {
+            return;
+        }
+        for key in ["c", "z", "l"] {
+            if let Some(rules) = self.rules.get_mut(key) {
+                rules.retain(|r| {
+                    !matches!((r.pattern.as_str(), r.phonemes.as_str()), ("c", "T" | "s") | ("z" | "ll", _))
+                });
+            }
+        }
+
+        let (sibilant, palatal) = match variant {
+            SpanishVariant::Castilian => ("T", "L"),
+            SpanishVariant::LatinAmerican => ("s", "j"),
+        };
+        self.add_rule("ll", "", "", palatal, 20);
+        self.add_rule("c", "", "[ei]", sibilant, 10);
+        self.add_rule("z", "", "", sibilant, 1);
+    }
+
     /// Load English G2P rules.
     fn load_english_rules(&mut self) {
         // Basic vowel rules
-        self.add_rule("a", "", "e$", "e", 10); // 'ate' -> /eɪt/
+        self.add_rule("a", "", "C?e$", "e", 10); // 'ate' -> /eɪt/
         self.add_rule("a", "", "", "&", 1);    // default 'a' -> /æ/
-        self.add_rule("e", "", "e$", "i", 10); // 'ee' at end
+        self.add_rule("e", "", "C?e$", "i", 10); // 'these', 'ee' at end
         self.add_rule("e", "", "$", "", 5);    // silent 'e' at end
         self.add_rule("e", "", "", "E", 1);    // default 'e' -> /ɛ/
-        self.add_rule("i", "", "e$", "aI", 10); // 'ite' -> /aɪt/
+        self.add_rule("i", "", "C?e$", "aI", 10); // 'ite' -> /aɪt/
         self.add_rule("i", "", "", "I", 1);    // default 'i' -> /ɪ/
-        self.add_rule("o", "", "e$", "o", 10); // 'ose' -> /oʊz/
+        self.add_rule("o", "", "C?e$", "o", 10); // 'ose' -> /oʊz/
         self.add_rule("o", "", "", "A", 1);    // default 'o' -> /ɑ/
-        self.add_rule("u", "", "e$", "u", 10); // 'ute' -> /uːt/
+        self.add_rule("u", "", "C?e$", "u", 10); // 'ute' -> /uːt/
         self.add_rule("u", "", "", "@", 1);    // default 'u' -> /ʌ/
 
         // Consonant combinations
@@ -88,11 +479,11 @@ impl G2PConverter {
 
         // Single consonants
         self.add_rule("b", "", "", "b", 1);
-        self.add_rule("c", "", "[ei]", "s", 10); // soft c
+        self.add_rule("c", "", "[eiy]", "s", 10); // soft c
         self.add_rule("c", "", "", "k", 1);      // hard c
         self.add_rule("d", "", "", "d", 1);
         self.add_rule("f", "", "", "f", 1);
-        self.add_rule("g", "", "[ei]", "dZ", 8); // soft g (sometimes)
+        self.add_rule("g", "", "[eiy]", "dZ", 8); // soft g (sometimes)
         self.add_rule("g", "", "", "g", 1);      // hard g
         self.add_rule("h", "", "", "h", 1);
         self.add_rule("j", "", "", "dZ", 1);
@@ -107,8 +498,9 @@ impl G2PConverter {
         self.add_rule("t", "", "", "t", 1);
         self.add_rule("v", "", "", "v", 1);
         self.add_rule("w", "", "", "w", 1);
-        self.add_rule("x", "", "", "ks", 1);
+        self.add_rule("x", "", "", "k s", 1);
         self.add_rule("y", "^", "", "j", 10);    // 'y' at start -> /j/
+        self.add_rule("y", "^[^aeiou]+", "$", "aI", 12); // 'my', 'fly' -> /aɪ/
         self.add_rule("y", "", "", "i", 1);      // 'y' elsewhere -> /ɪ/
         self.add_rule("z", "", "", "z", 1);
 
@@ -118,6 +510,7 @@ impl G2PConverter {
         self.add_rule("oo", "", "", "u", 15);
         self.add_rule("ou", "", "", "aU", 15);
         self.add_rule("ow", "", "", "aU", 10);
+        self.add_rule("igh", "", "", "aI", 25); // 'high', 'night'
         self.add_rule("oi", "", "", "OI", 15);
         self.add_rule("oy", "", "", "OI", 15);
         self.add_rule("ai", "", "", "e", 15);
@@ -152,6 +545,94 @@ impl G2PConverter {
         self.exceptions.insert("two".to_string(), "t u".to_string());
         self.exceptions.insert("hello".to_string(), "h E l o".to_string());
         self.exceptions.insert("world".to_string(), "w 3 r l d".to_string());
+        // Words that look like regular inflections but are not
+        self.exceptions.insert("hundred".to_string(), "h @ n d r I d".to_string());
+        self.exceptions.insert("naked".to_string(), "n e k I d".to_string());
+        self.exceptions.insert("wicked".to_string(), "w I k I d".to_string());
+        self.exceptions.insert("united".to_string(), "j u n aI t I d".to_string());
+        self.exceptions.insert("chaos".to_string(), "k e A s".to_string());
+        self.exceptions.insert("bias".to_string(), "b aI @ s".to_string());
+        // Final 'e' that does not lengthen the vowel
+        self.exceptions.insert("come".to_string(), "k @ m".to_string());
+        self.exceptions.insert("some".to_string(), "s @ m".to_string());
+        self.exceptions.insert("done".to_string(), "d @ n".to_string());
+        self.exceptions.insert("gone".to_string(), "g O n".to_string());
+        self.exceptions.insert("love".to_string(), "l @ v".to_string());
+        self.exceptions.insert("give".to_string(), "g I v".to_string());
+        self.exceptions.insert("live".to_string(), "l I v".to_string());
+        self.exceptions.insert("there".to_string(), "D E r".to_string());
+        self.exceptions.insert("where".to_string(), "w E r".to_string());
+    }
+
+    /// Load English initialisms that are pronounced as words.
+    fn load_english_initialisms(&mut self) {
+        self.add_spoken_initialism("sql", Some("s i k w @ l"));
+        self.add_spoken_initialism("scsi", Some("s k @ z i"));
+        self.add_spoken_initialism("nasa", Some("n & s @"));
+        self.add_spoken_initialism("nato", Some("n e t o"));
+        self.add_spoken_initialism("jpeg", Some("dZ e p E g"));
+        self.add_spoken_initialism("ascii", Some("& s k i"));
+        self.add_spoken_initialism("gif", None);
+    }
+
+    /// Load Spanish initialisms that are pronounced as words.
+    fn load_spanish_initialisms(&mut self) {
+        self.add_spoken_initialism("onu", None);
+        self.add_spoken_initialism("otan", None);
+        self.add_spoken_initialism("ovni", None);
+        self.add_spoken_initialism("sida", None);
+    }
+
+    /// Adds a word to the exception dictionary, overriding the rules.
+    ///
+    /// `phonemes` are space-separated inventory symbols. The word is matched
+    /// case-insensitively, like the built-in exceptions.
+    pub fn add_exception(&mut self, word: &str, phonemes: &str) {
+        let phonemes = phonemes.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.exceptions.insert(self.normalize(word), phonemes);
+    }
+
+    /// Loads exceptions from a pronunciation dictionary, one
+    /// `word<TAB>phonemes` entry per line.
+    ///
+    /// Blank lines and lines starting with `#` are skipped. Returns the
+    /// number of entries loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PhonemeError` for a line without a tab and `InvalidPhoneme`
+    /// for a symbol not in the inventory. Nothing is loaded if any line is
+    /// invalid.
+    pub fn load_exceptions_from_str(&mut self, text: &str) -> Result<usize> {
+        let mut entries = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, phonemes) = line.split_once('\t').ok_or_else(|| {
+                SynthesizerError::PhonemeError(format!("line {}: expected word<TAB>phonemes", index + 1))
+            })?;
+            if let Some(symbol) = phonemes.split_whitespace().find(|s| self.inventory.get(s).is_none()) {
+                return Err(SynthesizerError::InvalidPhoneme(format!("'{}' on line {}", symbol, index + 1)));
+            }
+            entries.push((word.trim(), phonemes));
+        }
+
+        for (word, phonemes) in &entries {
+            self.add_exception(word, phonemes);
+        }
+        Ok(entries.len())
+    }
+
+    /// Registers an all-caps word that is pronounced as a word instead of
+    /// being spelled out letter by letter.
+    ///
+    /// `phonemes` overrides the pronunciation; with `None` the word goes
+    /// through the normal rules.
+    pub fn add_spoken_initialism(&mut self, word: &str, phonemes: Option<&str>) {
+        self.spoken_initialisms
+            .insert(word.to_lowercase(), phonemes.map(str::to_string));
     }
 
     /// Load Spanish G2P rules.
@@ -173,14 +654,12 @@ impl G2PConverter {
 
         // Consonant combinations
         self.add_rule("ch", "", "", "tS", 20);
-        self.add_rule("ll", "", "", "L", 20);
         self.add_rule("rr", "", "", "rr", 20);
         self.add_rule("ñ", "", "", "J", 20);
         self.add_rule("qu", "", "[ei]", "k", 20);
         self.add_rule("gu", "", "[ei]", "g", 20);
 
-        // C rules
-        self.add_rule("c", "", "[ei]", "T", 10);  // ceceo/seseo (using ceceo)
+        // C rules ("ce", "ci", "z" and "ll" depend on the variant)
         self.add_rule("c", "", "", "k", 1);
 
         // G rules
@@ -204,18 +683,161 @@ impl G2PConverter {
         self.add_rule("t", "", "", "t", 1);
         self.add_rule("v", "", "", "b", 1);       // v = b in Spanish
         self.add_rule("w", "", "", "w", 1);
-        self.add_rule("x", "", "", "ks", 1);
+        self.add_rule("x", "", "", "k s", 1);
         self.add_rule("y", "", "$", "i", 10);     // 'y' at end -> /i/
         self.add_rule("y", "", "", "j", 1);       // 'y' elsewhere
-        self.add_rule("z", "", "", "T", 1);       // ceceo
+    }
+
+    /// Load French G2P rules.
+    fn load_french_rules(&mut self) {
+        // Vowel digraphs and trigraphs
+        self.add_rule("eau", "", "", "o", 30);
+        self.add_rule("au", "", "", "o", 20);
+        self.add_rule("ou", "", "", "u", 20);
+        self.add_rule("oi", "", "", "w a", 20);
+        self.add_rule("eu", "", "", "2", 20);
+        self.add_rule("œu", "", "", "9", 25);
+        self.add_rule("ai", "", "", "E", 20);
+        self.add_rule("ei", "", "", "E", 20);
+        self.add_rule("ill", "", "", "i j", 25);
+
+        // Nasal vowels: a vowel + n/m not followed by a vowel or another n/m
+        self.add_rule("ain", "", "#|[^Vmn]", "E~", 25);
+        self.add_rule("aim", "", "#|[^Vmn]", "E~", 25);
+        self.add_rule("ein", "", "#|[^Vmn]", "E~", 25);
+        self.add_rule("oin", "", "#|[^Vmn]", "w E~", 25);
+        self.add_rule("ien", "", "#|[^Vmn]", "j E~", 25);
+        self.add_rule("an", "", "#|[^Vmn]", "A~", 15);
+        self.add_rule("am", "", "#|[^Vmn]", "A~", 15);
+        self.add_rule("en", "", "#|[^Vmn]", "A~", 15);
+        self.add_rule("em", "", "#|[^Vmn]", "A~", 15);
+        self.add_rule("in", "", "#|[^Vmn]", "E~", 15);
+        self.add_rule("im", "", "#|[^Vmn]", "E~", 15);
+        self.add_rule("on", "", "#|[^Vmn]", "O~", 15);
+        self.add_rule("om", "", "#|[^Vmn]", "O~", 15);
+        self.add_rule("un", "", "#|[^Vmn]", "9~", 15);
+        self.add_rule("um", "", "#|[^Vmn]", "9~", 15);
+
+        // Word-final endings
+        self.add_rule("er", "", "$", "e", 15);    // parler
+        self.add_rule("ez", "", "$", "e", 15);    // parlez
+        self.add_rule("et", "", "$", "E", 15);    // billet
+        self.add_rule("es", "", "$", "", 10);     // silent plural -es
+        self.add_rule("e", "", "$", "", 5);       // silent final e
+
+        // Single vowels
+        self.add_rule("a", "", "", "a", 1);
+        self.add_rule("e", "", "", "@", 1);
+        self.add_rule("i", "", "", "i", 1);
+        self.add_rule("o", "", "", "O", 1);
+        self.add_rule("u", "", "", "y", 1);
+        self.add_rule("y", "", "", "i", 1);
+
+        // Accented vowels and ligatures
+        self.add_rule("é", "", "", "e", 1);
+        self.add_rule("è", "", "", "E", 1);
+        self.add_rule("ê", "", "", "E", 1);
+        self.add_rule("ë", "", "", "E", 1);
+        self.add_rule("à", "", "", "a", 1);
+        self.add_rule("â", "", "", "a", 1);
+        self.add_rule("î", "", "", "i", 1);
+        self.add_rule("ï", "", "", "i", 1);
+        self.add_rule("ô", "", "", "o", 1);
+        self.add_rule("û", "", "", "y", 1);
+        self.add_rule("ù", "", "", "y", 1);
+        self.add_rule("œ", "", "", "9", 1);
+
+        // Consonant combinations
+        self.add_rule("ch", "", "", "S", 20);
+        self.add_rule("gn", "", "", "J", 20);
+        self.add_rule("ph", "", "", "f", 20);
+        self.add_rule("qu", "", "", "k", 20);
+        self.add_rule("th", "", "", "t", 20);
+        self.add_rule("gu", "", "[ei]", "g", 20);
+        self.add_rule("cc", "", "", "k", 15);
+        self.add_rule("ff", "", "", "f", 15);
+        self.add_rule("ll", "", "", "l", 15);
+        self.add_rule("mm", "", "", "m", 15);
+        self.add_rule("nn", "", "", "n", 15);
+        self.add_rule("pp", "", "", "p", 15);
+        self.add_rule("rr", "", "", "R", 15);
+        self.add_rule("ss", "", "", "s", 15);
+        self.add_rule("tt", "", "", "t", 15);
+
+        // Silent final consonants
+        self.add_rule("d", "", "$", "", 5);
+        self.add_rule("p", "", "$", "", 5);
+        self.add_rule("s", "", "$", "", 5);
+        self.add_rule("t", "", "$", "", 5);
+        self.add_rule("x", "", "$", "", 5);
+        self.add_rule("z", "", "$", "", 5);
+
+        // Single consonants
+        self.add_rule("b", "", "", "b", 1);
+        self.add_rule("c", "", "[ei]", "s", 10);  // soft c
+        self.add_rule("c", "", "", "k", 1);
+        self.add_rule("ç", "", "", "s", 1);
+        self.add_rule("d", "", "", "d", 1);
+        self.add_rule("f", "", "", "f", 1);
+        self.add_rule("g", "", "[ei]", "Z", 10);  // soft g
+        self.add_rule("g", "", "", "g", 1);
+        self.add_rule("h", "", "", "", 1);        // silent h
+        self.add_rule("j", "", "", "Z", 1);
+        self.add_rule("k", "", "", "k", 1);
+        self.add_rule("l", "", "", "l", 1);
+        self.add_rule("m", "", "", "m", 1);
+        self.add_rule("n", "", "", "n", 1);
+        self.add_rule("p", "", "", "p", 1);
+        self.add_rule("r", "", "", "R", 1);
+        self.add_rule("s", "V", "V", "z", 10);  // s between vowels
+        self.add_rule("s", "", "", "s", 1);
+        self.add_rule("t", "", "", "t", 1);
+        self.add_rule("v", "", "", "v", 1);
+        self.add_rule("w", "", "", "w", 1);
+        self.add_rule("x", "", "", "k s", 1);
+        self.add_rule("z", "", "", "z", 1);
+    }
+
+    /// Load French exception dictionary.
+    fn load_french_exceptions(&mut self) {
+        // Function words whose vowel the rules would drop or misread
+        self.exceptions.insert("le".to_string(), "l @".to_string());
+        self.exceptions.insert("de".to_string(), "d @".to_string());
+        self.exceptions.insert("je".to_string(), "Z @".to_string());
+        self.exceptions.insert("me".to_string(), "m @".to_string());
+        self.exceptions.insert("te".to_string(), "t @".to_string());
+        self.exceptions.insert("se".to_string(), "s @".to_string());
+        self.exceptions.insert("ne".to_string(), "n @".to_string());
+        self.exceptions.insert("ce".to_string(), "s @".to_string());
+        self.exceptions.insert("que".to_string(), "k @".to_string());
+        self.exceptions.insert("les".to_string(), "l e".to_string());
+        self.exceptions.insert("des".to_string(), "d e".to_string());
+        self.exceptions.insert("mes".to_string(), "m e".to_string());
+        self.exceptions.insert("tes".to_string(), "t e".to_string());
+        self.exceptions.insert("ses".to_string(), "s e".to_string());
+        self.exceptions.insert("ces".to_string(), "s e".to_string());
+        self.exceptions.insert("et".to_string(), "e".to_string());
+        self.exceptions.insert("est".to_string(), "E".to_string());
+        self.exceptions.insert("une".to_string(), "y n".to_string());
+        self.exceptions.insert("oui".to_string(), "w i".to_string());
+        self.exceptions.insert("merci".to_string(), "m E R s i".to_string());
+        self.exceptions.insert("monsieur".to_string(), "m @ s j 2".to_string());
+    }
+
+    /// Load French initialisms that are pronounced as words.
+    fn load_french_initialisms(&mut self) {
+        self.add_spoken_initialism("onu", None);
+        self.add_spoken_initialism("otan", None);
+        self.add_spoken_initialism("ovni", None);
+        self.add_spoken_initialism("sida", None);
     }
 
     /// Add a G2P rule.
     fn add_rule(&mut self, pattern: &str, left_context: &str, right_context: &str, phonemes: &str, priority: i32) {
         let rule = G2PRule {
             pattern: pattern.to_string(),
-            left_context: left_context.to_string(),
-            right_context: right_context.to_string(),
+            left_context: RuleContext::parse(left_context, true),
+            right_context: RuleContext::parse(right_context, false),
             phonemes: phonemes.to_string(),
             priority,
         };
@@ -233,50 +855,417 @@ impl G2PConverter {
     }
 
     /// Convert text to phoneme sequence.
+    ///
+    /// Words are separated by `_`; line breaks and blank-line separated
+    /// paragraphs are marked by runs of several `_` gaps.
     pub fn convert(&self, text: &str) -> Result<String> {
-        let normalized = self.normalize(text);
-        let words: Vec<&str> = normalized.split_whitespace().collect();
+        self.convert_text(text, false, None).map(|report| report.phonemes)
+    }
+
+    /// Convert text to phonemes, also reporting letters that were skipped
+    /// because no rule covers them.
+    pub fn convert_verbose(&self, text: &str) -> Result<ConversionReport> {
+        self.convert_text(text, false, None)
+    }
+
+    /// Convert text to a phoneme sequence annotated with syllable structure.
+    ///
+    /// Same as [`convert`](Self::convert), but polysyllabic words carry a `'`
+    /// token before their primary-stressed syllable, `,` before syllables
+    /// with secondary stress and `.` before every other syllable boundary
+    /// (see [`StressLevel`]). Shouted words (see
+    /// [`set_caps_policy`](Self::set_caps_policy)) start with a `!` token,
+    /// quoted words (see [`set_quote_detection`](Self::set_quote_detection))
+    /// with a `"` token, words of parenthetical phrases (see
+    /// [`set_parenthetical_detection`](Self::set_parenthetical_detection))
+    /// with a `(` token, and sentences end with a `?` token for questions
+    /// or `|` otherwise.
+    pub fn convert_marked(&self, text: &str) -> Result<String> {
+        self.convert_text(text, true, None).map(|report| report.phonemes)
+    }
+
+    /// Same as [`convert_marked`](Self::convert_marked), also returning for
+    /// each word of the output the index of the whitespace-separated token
+    /// of `text` it was read from.
+    pub(crate) fn convert_marked_with_sources(&self, text: &str) -> Result<(String, Vec<usize>)> {
+        let mut sources = Vec::new();
+        let report = self.convert_text(text, true, Some(&mut sources))?;
+        Ok((report.phonemes, sources))
+    }
+
+    /// Split a phoneme sequence into syllables, each returned as its
+    /// space-separated tokens.
+    ///
+    /// Every syllable has one vowel nucleus, and consonants between two
+    /// nuclei go to the second syllable as far as they form a legal onset
+    /// (maximal onset). Syllables never cross word gaps or sentence ends;
+    /// stress and syllable marks in the input are ignored.
+    pub fn syllabify(&self, phonemes: &str) -> Vec<String> {
+        let tokens: Vec<&str> = phonemes
+            .split_whitespace()
+            .filter(|t| !matches!(*t, "'" | "," | "." | EMPHASIS_MARK | QUOTE_MARK | PARENTHETICAL_MARK))
+            .collect();
+        tokens
+            .split(|t| matches!(*t, "_" | QUESTION_MARK | STATEMENT_MARK))
+            .flat_map(|word| self.split_syllables(word))
+            .map(|syllable| syllable.join(" "))
+            .collect()
+    }
+
+    /// Convert text to IPA using this converter's rules.
+    ///
+    /// Reusing one converter avoids rebuilding the rule tables for every
+    /// call, which matters when transcribing many texts.
+    pub fn to_ipa(&self, text: &str, options: &IpaOptions) -> Result<String> {
+        let phonemes = if options.syllable_marks || options.stress_marks {
+            self.convert_marked(text)?
+        } else {
+            self.convert(text)?
+        };
+    
+        // Convert ASCII phonemes to IPA
+        let inventory = self.inventory();
+        let mut symbols: Vec<&str> = phonemes.split_whitespace().collect();
+        symbols.dedup_by(|a, b| *a == "_" && *b == "_");
+
+        let mut ipa = String::new();
+        let mut word_start = true;
+        for symbol in symbols {
+            match symbol {
+                "_" => {
+                    ipa.push(options.word_separator);
+                    word_start = true;
+                }
+                "'" if options.stress_marks => ipa.push('ˈ'),
+                "," if options.stress_marks => ipa.push('ˌ'),
+                EMPHASIS_MARK | QUOTE_MARK | PARENTHETICAL_MARK | QUESTION_MARK | STATEMENT_MARK => {}
+                "'" | "," | "." => {
+                    if options.syllable_marks && !word_start {
+                        ipa.push('.');
+                    }
+                }
+                p => {
+                    ipa.push_str(inventory.get(p).map_or(p, |phoneme| phoneme.ipa));
+                    word_start = false;
+                }
+            }
+        }
+
+        Ok(ipa)
+    }
+
+    /// Convert text, optionally annotating syllables and stress, and
+    /// recording the source token of each output word to `sources` if given.
+    fn convert_text(
+        &self,
+        text: &str,
+        marked: bool,
+        mut sources: Option<&mut Vec<usize>>,
+    ) -> Result<ConversionReport> {
         let mut result = Vec::new();
+        let mut dropped = Vec::new();
+        let mut gap = 1;
+        // Index of the line's first token in the whole text
+        let mut line_start = 0;
 
-        for word in words {
-            let phonemes = self.convert_word(word)?;
-            if !phonemes.is_empty() {
-                result.push(phonemes);
+        for line in text.lines() {
+            if line.trim().is_empty() {
+                gap = gap.max(self.paragraph_pause);
+                continue;
+            }
+
+            let expanded = self.expand_numbers(line);
+            let tokens: Vec<&str> = expanded.iter().map(|(_, word)| word.as_str()).collect();
+            let quoted = if marked && self.quote_detection {
+                quoted_tokens(&tokens)
+            } else {
+                vec![false; tokens.len()]
+            };
+            let aside = if marked && self.parenthetical_detection {
+                parenthetical_tokens(&tokens)
+            } else {
+                vec![false; tokens.len()]
+            };
+            for (i, &token) in tokens.iter().enumerate() {
+                if aside[i] && (i == 0 || !aside[i - 1]) {
+                    gap = gap.max(PARENTHETICAL_PAUSE);
+                }
+                // Digits that number expansion left alone are stripped here
+                for c in token.chars().filter(|c| c.is_numeric()) {
+                    if !dropped.contains(&c) {
+                        dropped.push(c);
+                    }
+                }
+                let word = self.normalize(token);
+                let caps = is_initialism(token);
+                let spell = caps
+                    && match self.caps_policy {
+                        CapsPolicy::Acronym => true,
+                        CapsPolicy::Shout => {
+                            self.spoken_initialisms.contains_key(&word) || !is_pronounceable(&word)
+                        }
+                        CapsPolicy::Ignore => false,
+                    };
+                for piece in self.split_foreign(&word, &mut dropped) {
+                    let Some(word) = piece else {
+                        gap = gap.max(FOREIGN_TEXT_PAUSE);
+                        continue;
+                    };
+                    let mut phonemes = if spell {
+                        self.convert_initialism(&word, &mut dropped)?
+                    } else {
+                        let phonemes = self.convert_word(&word, &mut dropped)?;
+                        self.apply_flaps(&word, self.reduce_vowels(&word, phonemes))
+                    };
+                    if marked {
+                        phonemes = self.mark_syllables(&word, &phonemes);
+                        if caps && !spell && self.caps_policy == CapsPolicy::Shout && !phonemes.is_empty() {
+                            phonemes = format!("{} {}", EMPHASIS_MARK, phonemes);
+                        }
+                        if quoted[i] && !phonemes.is_empty() {
+                            phonemes = format!("{} {}", QUOTE_MARK, phonemes);
+                        }
+                        if aside[i] && !phonemes.is_empty() {
+                            phonemes = format!("{} {}", PARENTHETICAL_MARK, phonemes);
+                        }
+                    }
+                    if !phonemes.is_empty() {
+                        if !result.is_empty() {
+                            result.extend(std::iter::repeat_n("_".to_string(), gap));
+                        }
+                        if let Some(sources) = sources.as_deref_mut() {
+                            let words = phonemes.split('_').filter(|w| !w.trim().is_empty()).count();
+                            sources.extend(std::iter::repeat_n(line_start + expanded[i].0, words));
+                        }
+                        result.push(phonemes);
+                        gap = 1;
+                    }
+                }
+                if marked {
+                    if let (Some(mark), Some(last)) = (sentence_end_mark(token), result.last_mut()) {
+                        if !last.ends_with(QUESTION_MARK) && !last.ends_with(STATEMENT_MARK) {
+                            last.push(' ');
+                            last.push_str(mark);
+                        }
+                    }
+                }
+                if aside[i] && !aside.get(i + 1).copied().unwrap_or(false) {
+                    gap = gap.max(PARENTHETICAL_PAUSE);
+                }
+            }
+            gap = gap.max(self.line_break_pause);
+            line_start += line.split_whitespace().count();
+        }
+
+        if self.strict && !dropped.is_empty() {
+            let letters: Vec<String> = dropped.iter().map(|c| format!("'{}'", c)).collect();
+            return Err(SynthesizerError::PhonemeError(format!(
+                "no {} pronunciation rule for {}",
+                self.language,
+                letters.join(", ")
+            )));
+        }
+
+        Ok(ConversionReport {
+            phonemes: result.join(" "),
+            dropped,
+        })
+    }
+
+    /// Split a line into words, replacing numbers with their words ("42"
+    /// -> "forty two", "1,000" -> "one thousand"). Each word comes with the
+    /// index of the line's token it was read from.
+    ///
+    /// Runs before normalization, which would otherwise strip the digits.
+    fn expand_numbers(&self, line: &str) -> Vec<(usize, String)> {
+        let mut words = Vec::new();
+        for (i, token) in line.split_whitespace().enumerate() {
+            match self.expand_number(token) {
+                Some(expanded) => words.extend(expanded.split_whitespace().map(|word| (i, word.to_string()))),
+                None => words.push((i, token.to_string())),
             }
         }
+        words
+    }
+
+    /// Spell out a token that is a number, keeping surrounding punctuation.
+    ///
+    /// Thousands may be grouped with the language's separator (`,` in
+    /// English, `.` in Spanish), and a decimal point (`.` in English, `,`
+    /// in Spanish) is read out with the digits after it one by one ("3.14"
+    /// -> "three point one four"). Digits split any other way, such as
+    /// "1,5" in English, are read as separate numbers ("one five"). A
+    /// leading minus sign is read out, and in
+    /// English a plain four-digit number is read as a year ("1999" ->
+    /// "nineteen ninety nine").
+    fn expand_number(&self, token: &str) -> Option<String> {
+        let start = token.find(|c: char| c.is_ascii_digit())?;
+        let end = token.rfind(|c: char| c.is_ascii_digit())? + 1;
+        let (prefix, digits, suffix) = (&token[..start], &token[start..end], &token[end..]);
+        if prefix.chars().chain(suffix.chars()).any(char::is_alphanumeric) {
+            return None;
+        }
+        let (prefix, negative) = match prefix.strip_suffix(['-', '\u{2212}']) {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
 
-        Ok(result.join(" _ "))
+        let (separator, number_words, minus): (char, fn(u64) -> String, &str) = match self.language.as_str() {
+            "en" => (',', english_number_words, "minus"),
+            "es" => ('.', spanish_number_words, "menos"),
+            _ => return None,
+        };
+        // The other separator is the decimal point
+        let (point, point_word) = if separator == ',' { ('.', "point") } else { (',', "coma") };
+        let words = match parse_grouped_number(digits, separator) {
+            Some(number) if !negative && self.language == "en" && digits.len() == 4 && (1000..10000).contains(&number) => {
+                english_year_words(number)
+            }
+            Some(number) => number_words(number),
+            None => match digits
+                .split_once(point)
+                .filter(|(_, fraction)| fraction.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|(whole, fraction)| Some((parse_grouped_number(whole, separator)?, fraction)))
+            {
+                Some((whole, fraction)) => {
+                    let fraction: Vec<String> = fraction.bytes().map(|b| number_words(u64::from(b - b'0'))).collect();
+                    format!("{} {} {}", number_words(whole), point_word, fraction.join(" "))
+                }
+                // Not clearly grouped or decimal: each run of digits on its own
+                None => digits
+                    .split([separator, point])
+                    .map(|group| group.parse().ok().filter(|&n| n <= MAX_SPOKEN_NUMBER).map(number_words))
+                    .collect::<Option<Vec<_>>>()?
+                    .join(" "),
+            },
+        };
+        let words = if negative { format!("{} {}", minus, words) } else { words };
+        Some(format!("{}{}{}", prefix, words, suffix))
     }
 
     /// Normalize text for processing.
     fn normalize(&self, text: &str) -> String {
         text.to_lowercase()
             .chars()
+            .map(|c| if c == '\u{2019}' { '\'' } else { c })  // typographic apostrophe
             .filter(|c| c.is_alphabetic() || c.is_whitespace() || *c == '\'' || *c == '-')
             .collect()
     }
 
-    /// Convert a single word to phonemes.
-    fn convert_word(&self, word: &str) -> Result<String> {
+    /// Split a normalized word around runs of letters without rules,
+    /// according to the foreign text policy.
+    ///
+    /// `None` pieces stand for a run to be replaced by a pause; the letters
+    /// in it are recorded in `dropped`. Under `ForeignTextPolicy::Skip` the
+    /// word is returned whole and dropped letters are recorded during
+    /// conversion instead.
+    fn split_foreign(&self, word: &str, dropped: &mut Vec<char>) -> Vec<Option<String>> {
+        let policy = self.foreign_text_policy;
+        if policy == ForeignTextPolicy::Skip {
+            return vec![Some(word.to_string())];
+        }
+
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+        for c in word.chars() {
+            let c = match latin_base_letter(c) {
+                Some(base) if policy == ForeignTextPolicy::Transliterate && !self.has_rule(c) => base,
+                _ => c,
+            };
+            if c.is_alphabetic() && !self.has_rule(c) {
+                if !dropped.contains(&c) {
+                    dropped.push(c);
+                }
+                if !current.is_empty() {
+                    pieces.push(Some(std::mem::take(&mut current)));
+                }
+                if pieces.last() != Some(&None) {
+                    pieces.push(None);
+                }
+            } else {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            pieces.push(Some(current));
+        }
+        pieces
+    }
+
+    /// Returns true if some rule starts with this letter.
+    fn has_rule(&self, c: char) -> bool {
+        let mut key = [0u8; 4];
+        self.rules.contains_key(c.encode_utf8(&mut key) as &str)
+    }
+
+    /// Convert an all-caps word, spelling it out unless it is a known
+    /// initialism pronounced as a word.
+    fn convert_initialism(&self, word: &str, dropped: &mut Vec<char>) -> Result<String> {
+        match self.spoken_initialisms.get(word) {
+            Some(Some(phonemes)) => Ok(phonemes.clone()),
+            Some(None) => self.convert_word(word, dropped),
+            None => self.spell_word(word, dropped),
+        }
+    }
+
+    /// Spell a word letter by letter using the language's letter names.
+    fn spell_word(&self, word: &str, dropped: &mut Vec<char>) -> Result<String> {
+        let mut letters = Vec::new();
+        for letter in word.chars().filter(|c| c.is_alphabetic()) {
+            let name = match self.language.as_str() {
+                "en" => english_letter_name(letter).map(|phonemes| Ok(phonemes.to_string())),
+                "fr" => french_letter_name(letter).map(|name| self.convert_word(name, dropped)),
+                _ => spanish_letter_name(letter).map(|name| self.convert_word(name, dropped)),
+            };
+            let phonemes = name.unwrap_or_else(|| self.convert_word(&letter.to_string(), dropped))?;
+            if !phonemes.is_empty() {
+                letters.push(phonemes);
+            }
+        }
+        Ok(letters.join(" "))
+    }
+
+    /// Convert a single word to phonemes, recording letters no rule covers.
+    fn convert_word(&self, word: &str, dropped: &mut Vec<char>) -> Result<String> {
         // Check exceptions first
         if let Some(phonemes) = self.exceptions.get(word) {
             return Ok(phonemes.clone());
         }
 
+        // Possessives: "dog's", "James's", "dogs'"
+        if self.language == "en" {
+            if let Some(stem) = word.strip_suffix("'s") {
+                let stem_phonemes = self.convert_word(stem, dropped)?;
+                return Ok(self.append_s_suffix(stem_phonemes));
+            }
+            if let Some(stem) = word.strip_suffix('\'') {
+                return self.convert_word(stem, dropped);
+            }
+            // Regular inflections: "walked", "wanted", "dogs", "boxes"
+            if let Some((stem, suffix)) = split_inflection(word) {
+                let stem_phonemes = self.convert_word(&stem, dropped)?;
+                return Ok(match suffix {
+                    Inflection::Past => self.append_ed_suffix(stem_phonemes),
+                    Inflection::Plural => self.append_s_suffix(stem_phonemes),
+                });
+            }
+        }
+
         let chars: Vec<char> = word.chars().collect();
         let mut result = Vec::new();
         let mut i = 0;
 
         while i < chars.len() {
-            let remaining = &word[word.char_indices().nth(i).map(|(idx, _)| idx).unwrap_or(word.len())..];
-            
-            if let Some((phonemes, consumed)) = self.apply_rules(&chars, i, remaining) {
+            if let Some((phonemes, consumed)) = self.apply_rules(&chars, i) {
                 if !phonemes.is_empty() {
                     result.push(phonemes);
                 }
                 i += consumed;
             } else {
                 // No rule matched, skip character
+                if chars[i].is_alphabetic() && !dropped.contains(&chars[i]) {
+                    dropped.push(chars[i]);
+                }
                 i += 1;
             }
         }
@@ -284,58 +1273,274 @@ impl G2PConverter {
         Ok(result.join(" "))
     }
 
-    /// Apply G2P rules at the current position.
-    fn apply_rules(&self, chars: &[char], pos: usize, remaining: &str) -> Option<(String, usize)> {
-        let current_char = chars.get(pos)?.to_string();
-        
-        if let Some(rules) = self.rules.get(&current_char) {
-            for rule in rules {
-                if remaining.starts_with(&rule.pattern) {
-                    // Check contexts
-                    if self.check_left_context(&rule.left_context, chars, pos)
-                        && self.check_right_context(&rule.right_context, remaining, rule.pattern.len())
-                    {
-                        return Some((rule.phonemes.clone(), rule.pattern.chars().count()));
-                    }
+    /// Replace full vowels in a word's unstressed syllables with schwa, when
+    /// vowel reduction applies. Exception entries, and words whose stress is
+    /// only guessed ("banana"), are left as written.
+    fn reduce_vowels(&self, word: &str, phonemes: String) -> String {
+        if self.language != "en" || !self.vowel_reduction || self.exceptions.contains_key(word) {
+            return phonemes;
+        }
+        let tokens: Vec<&str> = phonemes.split_whitespace().collect();
+        let syllables = self.split_syllables(&tokens);
+        if syllables.len() < 2 || self.known_stress(word, syllables.len()).is_none() {
+            return phonemes;
+        }
+
+        let levels = self.stress_levels(word, syllables.len());
+        let mut reduced = Vec::with_capacity(tokens.len());
+        for (syllable, level) in syllables.iter().zip(levels) {
+            for &token in syllable {
+                let reducible = matches!(token, "&" | "A" | "E" | "O");
+                reduced.push(if reducible && level == StressLevel::Unstressed { "@" } else { token });
+            }
+        }
+        reduced.join(" ")
+    }
+
+    /// Replace English /t/ and /d/ between a vowel (or /r/) and an
+    /// unstressed vowel with a flap, when flapping applies. A doubled
+    /// letter ("butter" -> `b @ t t @ r`) becomes a single flap. Words
+    /// whose stress is only guessed ("today", "hotel") are left alone.
+    fn apply_flaps(&self, word: &str, phonemes: String) -> String {
+        if self.language != "en" || !self.flapping || self.exceptions.contains_key(word) {
+            return phonemes;
+        }
+        let tokens: Vec<&str> = phonemes.split_whitespace().collect();
+        let mut syllables = self.split_syllables(&tokens);
+        if syllables.len() < 2 || self.known_stress(word, syllables.len()).is_none() {
+            return phonemes;
+        }
+
+        let levels = self.stress_levels(word, syllables.len());
+        for i in 1..syllables.len() {
+            let onset = syllables[i][0];
+            if levels[i] != StressLevel::Unstressed
+                || !matches!(onset, "t" | "d")
+                || !syllables[i].get(1).is_some_and(|t| self.is_vowel(t))
+            {
+                continue;
+            }
+            let prev = &mut syllables[i - 1];
+            let doubled = prev.len() > 1 && prev.last() == Some(&onset);
+            let coda = if doubled { prev[prev.len() - 2] } else { prev[prev.len() - 1] };
+            if self.is_vowel(coda) || coda == "r" {
+                if doubled {
+                    prev.pop();
                 }
+                syllables[i][0] = "4";
+            }
+        }
+        syllables.concat().join(" ")
+    }
+
+    /// Insert stress (`'`) and syllable boundary (`.`) tokens into a word's phonemes.
+    fn mark_syllables(&self, word: &str, phonemes: &str) -> String {
+        let tokens: Vec<&str> = phonemes.split_whitespace().collect();
+        let syllables = self.split_syllables(&tokens);
+        if syllables.len() < 2 {
+            return phonemes.to_string();
+        }
+
+        let levels = self.stress_levels(word, syllables.len());
+        let mut marked = Vec::with_capacity(tokens.len() + syllables.len());
+        for (i, (syllable, level)) in syllables.iter().zip(levels).enumerate() {
+            if i > 0 || level != StressLevel::Unstressed {
+                marked.push(level.mark());
             }
+            marked.extend(syllable);
         }
+        marked.join(" ")
+    }
 
-        // Fallback: skip unrecognized character
-        Some((String::new(), 1))
+    /// Assign a stress level to each syllable of a word.
+    ///
+    /// Secondary stress alternates leftwards from the primary stress, on
+    /// every other syllable ("or.ga.ni.za.tion" -> `, . , ' .`).
+    fn stress_levels(&self, word: &str, syllables: usize) -> Vec<StressLevel> {
+        let primary = self.primary_stress(word, syllables);
+        (0..syllables)
+            .map(|i| {
+                if i == primary {
+                    StressLevel::Primary
+                } else if i < primary && (primary - i) % 2 == 0 {
+                    StressLevel::Secondary
+                } else {
+                    StressLevel::Unstressed
+                }
+            })
+            .collect()
     }
 
-    /// Check left context pattern.
-    fn check_left_context(&self, context: &str, _chars: &[char], pos: usize) -> bool {
-        if context.is_empty() {
-            return true;
+    /// Split a word's phoneme tokens into syllables using the maximal onset principle.
+    fn split_syllables<'a>(&self, tokens: &[&'a str]) -> Vec<Vec<&'a str>> {
+        let nuclei: Vec<usize> = (0..tokens.len()).filter(|&i| self.is_nucleus(tokens, i)).collect();
+        if nuclei.len() < 2 {
+            return if tokens.is_empty() { Vec::new() } else { vec![tokens.to_vec()] };
         }
-        
-        match context {
-            "^" => pos == 0,  // Start of word
-            _ => true,        // TODO: Implement more complex patterns
+
+        let mut starts = vec![0];
+        for pair in nuclei.windows(2) {
+            // Glides right before a nucleus belong to its syllable ("can.ción")
+            let glides = tokens[pair[0] + 1..pair[1]].iter().rev().take_while(|t| self.is_vowel(t)).count();
+            let cluster = &tokens[pair[0] + 1..pair[1] - glides];
+            let onset = (0..=cluster.len())
+                .rev()
+                .find(|&n| self.is_legal_onset(&cluster[cluster.len() - n..]))
+                .unwrap_or(0);
+            starts.push(pair[1] - glides - onset);
         }
+        starts.push(tokens.len());
+
+        starts.windows(2).map(|w| tokens[w[0]..w[1]].to_vec()).collect()
     }
 
-    /// Check right context pattern.
-    fn check_right_context(&self, context: &str, remaining: &str, pattern_len: usize) -> bool {
-        if context.is_empty() {
-            return true;
+    /// Returns true if the token at `i` is a syllable nucleus.
+    ///
+    /// In Spanish an `i`/`u` next to another vowel is a glide and joins
+    /// that vowel's syllable (diphthong).
+    fn is_nucleus(&self, tokens: &[&str], i: usize) -> bool {
+        if !self.is_vowel(tokens[i]) {
+            return false;
+        }
+        if self.language == "es" && matches!(tokens[i], "i" | "u") {
+            let next_is_vowel = tokens.get(i + 1).is_some_and(|t| self.is_vowel(t) && *t != tokens[i]);
+            let prev_is_full_vowel = i > 0
+                && self.is_vowel(tokens[i - 1])
+                && !matches!(tokens[i - 1], "i" | "u");
+            return !next_is_vowel && !prev_is_full_vowel;
         }
+        true
+    }
+
+    /// Returns true if the phoneme token is a vowel or diphthong.
+    fn is_vowel(&self, token: &str) -> bool {
+        self.inventory.get(token).is_some_and(|p| {
+            matches!(p.category, PhonemeCategory::Vowel | PhonemeCategory::Diphthong)
+        })
+    }
 
-        let after_pattern = &remaining[remaining.char_indices()
-            .nth(pattern_len)
-            .map(|(idx, _)| idx)
-            .unwrap_or(remaining.len())..];
+    /// Returns true if the consonant cluster can start a syllable.
+    fn is_legal_onset(&self, onset: &[&str]) -> bool {
+        let category = |t: &str| self.inventory.get(t).map(|p| p.category);
+        let is_obstruent = |t: &str| {
+            matches!(category(t), Some(PhonemeCategory::Plosive | PhonemeCategory::Fricative))
+        };
+        let is_liquid = |t: &str| {
+            matches!(
+                category(t),
+                Some(PhonemeCategory::Lateral | PhonemeCategory::Rhotic | PhonemeCategory::Approximant)
+            )
+        };
+        let is_stop = |t: &str| {
+            matches!(category(t), Some(PhonemeCategory::Plosive | PhonemeCategory::Nasal))
+        };
+        let english = self.language == "en";
 
-        match context {
-            "$" => after_pattern.is_empty(),  // End of word
-            "[ei]" => after_pattern.starts_with('e') || after_pattern.starts_with('i'),
-            "e$" => after_pattern == "e",     // 'e' at end
-            _ => true,  // TODO: Implement more patterns
+        match onset {
+            [] | [_] => true,
+            [a, b] => (is_obstruent(a) && is_liquid(b)) || (english && *a == "s" && is_stop(b)),
+            [a, b, c] => english && *a == "s" && is_stop(b) && is_liquid(c),
+            _ => false,
         }
     }
 
+    /// Index of the syllable carrying primary stress.
+    ///
+    /// English words no stress rule covers are guessed to stress their
+    /// first syllable.
+    fn primary_stress(&self, word: &str, syllables: usize) -> usize {
+        self.known_stress(word, syllables).unwrap_or(0)
+    }
+
+    /// Index of the stressed syllable, if a rule of the language places it.
+    ///
+    /// Returns `None` for English words that no suffix rule or word list
+    /// covers; passes that would go wrong on a guess (vowel reduction,
+    /// flapping) leave those words alone.
+    fn known_stress(&self, word: &str, syllables: usize) -> Option<usize> {
+        let last = syllables - 1;
+        match self.language.as_str() {
+            // French stresses the last syllable of the word
+            "fr" => Some(last),
+            // A written accent marks the stressed syllable; otherwise words
+            // ending in a vowel, n or s stress the penultimate syllable
+            "es" => {
+                if let Some(accented) = spanish_accented_syllable(word) {
+                    Some(accented.min(last))
+                } else if word.ends_with(|c: char| "aeiouns".contains(c)) {
+                    Some(last - 1)
+                } else {
+                    Some(last)
+                }
+            }
+            // Stress-attracting suffixes, unstressed prefixes ("about",
+            // "before") and unstressed endings ("butter", "little")
+            _ => {
+                if ["tion", "sion", "cian", "ic", "ial", "ian"].iter().any(|s| word.ends_with(s)) {
+                    Some(last - 1)
+                } else if ["ity", "ical"].iter().any(|s| word.ends_with(s)) {
+                    Some(last.saturating_sub(2))
+                } else if syllables == 2 && has_unstressed_prefix(word) {
+                    Some(1)
+                } else if syllables == 2 && has_unstressed_ending(word) {
+                    Some(0)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Append an "-s" suffix to a stem's phonemes, voiced after voiced
+    /// sounds and with an extra vowel after sibilants ("dog's", "cat's", "James's").
+    fn append_s_suffix(&self, stem: String) -> String {
+        let suffix = match stem.split_whitespace().last() {
+            None => return stem,
+            Some("s" | "z" | "S" | "Z" | "tS" | "dZ") => "I z",
+            Some(last) if self.inventory.get(last).is_some_and(|p| !p.voiced) => "s",
+            Some(_) => "z",
+        };
+        format!("{} {}", stem, suffix)
+    }
+
+    /// Append an "-ed" suffix to a stem's phonemes: /t/ after voiceless
+    /// sounds, /d/ after voiced ones and /ɪd/ after /t/ and /d/ ("walked",
+    /// "played", "wanted").
+    fn append_ed_suffix(&self, stem: String) -> String {
+        let suffix = match stem.split_whitespace().last() {
+            None => return stem,
+            Some("t" | "d") => "I d",
+            Some(last) if self.inventory.get(last).is_some_and(|p| !p.voiced) => "t",
+            Some(_) => "d",
+        };
+        format!("{} {}", stem, suffix)
+    }
+
+    /// Apply G2P rules at the current position.
+    ///
+    /// Works on char indices throughout, so multi-byte letters ("ñ", "á")
+    /// never split a UTF-8 sequence.
+    fn apply_rules(&self, chars: &[char], pos: usize) -> Option<(String, usize)> {
+        let mut key = [0u8; 4];
+        let current_char = chars.get(pos)?.encode_utf8(&mut key);
+        let remaining = &chars[pos..];
+        // Letters before the match, nearest first, as left contexts read them
+        let before: Vec<char> = chars[..pos].iter().rev().copied().collect();
+
+        for rule in self.rules.get(current_char as &str)? {
+            let pattern_len = rule.pattern.chars().count();
+            if remaining.len() >= pattern_len
+                && rule.pattern.chars().zip(remaining).all(|(p, c)| p == *c)
+                && rule.left_context.matches(&before)
+                && rule.right_context.matches(&remaining[pattern_len..])
+            {
+                return Some((rule.phonemes.clone(), pattern_len));
+            }
+        }
+
+        None
+    }
+
     /// Get the phoneme inventory.
     pub fn inventory(&self) -> &PhonemeInventory {
         &self.inventory
@@ -347,32 +1552,538 @@ impl G2PConverter {
     }
 }
 
+/// Sentence-final mark for a token ending a sentence ("why?", "no.").
+fn sentence_end_mark(token: &str) -> Option<&'static str> {
+    let token = token.trim_end_matches(['"', '\'', ')', ']', '»', '\u{201D}', '\u{2019}']);
+    if token.ends_with('?') {
+        Some(QUESTION_MARK)
+    } else if token.ends_with(['.', '!', '\u{2026}']) {
+        Some(STATEMENT_MARK)
+    } else {
+        None
+    }
+}
+
+/// Which tokens of a line lie between a matched pair of quotation marks.
+///
+/// A token opens a quote when a quotation mark comes before its first
+/// letter or digit, and closes one when a mark follows its last; a token
+/// of punctuation alone opens or closes whichever is pending. An opening
+/// mark with no closing mark later on the line quotes nothing.
+fn quoted_tokens(tokens: &[&str]) -> Vec<bool> {
+    const OPENING: [char; 3] = ['"', '\u{201C}', '«'];
+    const CLOSING: [char; 3] = ['"', '\u{201D}', '»'];
+
+    let mut quoted = vec![false; tokens.len()];
+    let mut open = None;
+    for (i, token) in tokens.iter().enumerate() {
+        let body_start = token.find(char::is_alphanumeric);
+        let body_end = token
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_alphanumeric())
+            .map(|(i, c)| i + c.len_utf8());
+        let (prefix, suffix) = match (body_start, body_end) {
+            (Some(start), Some(end)) => (&token[..start], &token[end..]),
+            _ if open.is_none() => (*token, ""),
+            _ => ("", *token),
+        };
+        if open.is_none() && prefix.contains(OPENING) {
+            open = Some(i);
+        }
+        if let Some(start) = open {
+            if suffix.contains(CLOSING) {
+                quoted[start..=i].fill(true);
+                open = None;
+            }
+        }
+    }
+    quoted
+}
+
+/// Which tokens of a line belong to a parenthetical phrase.
+///
+/// Parentheses are matched by depth, so a nested pair is part of the
+/// outermost phrase, and an unmatched opening one marks nothing. Outside
+/// parentheses, up to [`PARENTHETICAL_MAX_WORDS`] words between two commas
+/// form a phrase, as long as the sentence goes on after the second comma.
+/// A phrase starting with a conjunction is a clause, and one followed by a
+/// conjunction or another comma is a list item, so neither is marked.
+fn parenthetical_tokens(tokens: &[&str]) -> Vec<bool> {
+    let mut aside = vec![false; tokens.len()];
+
+    let mut depth = 0;
+    let mut open = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        let opening = token.chars().take_while(|c| !c.is_alphanumeric()).filter(|&c| c == '(').count();
+        let closing = token.chars().rev().take_while(|c| !c.is_alphanumeric()).filter(|&c| c == ')').count();
+        if depth == 0 && opening > 0 {
+            open = i;
+        }
+        depth += opening;
+        if depth > 0 && closing > 0 {
+            depth = depth.saturating_sub(closing);
+            if depth == 0 {
+                aside[open..=i].fill(true);
+            }
+        }
+    }
+
+    let ends_clause = |token: &str| token.ends_with(',') && sentence_end_mark(token).is_none();
+    let is_conjunction = |token: &str| {
+        let word = token.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        CONJUNCTIONS.contains(&word.as_str())
+    };
+    for start in 0..tokens.len() {
+        if !ends_clause(tokens[start]) || aside[start] {
+            continue;
+        }
+        let phrase = (start + 1..tokens.len().min(start + 1 + PARENTHETICAL_MAX_WORDS))
+            .take_while(|&i| !aside[i] && sentence_end_mark(tokens[i]).is_none())
+            .find(|&i| ends_clause(tokens[i]));
+        if let Some(end) = phrase {
+            let list_item = tokens.get(end + 1).is_some_and(|&next| is_conjunction(next) || ends_clause(next));
+            if end + 1 < tokens.len() && !is_conjunction(tokens[start + 1]) && !list_item {
+                aside[start + 1..=end].fill(true);
+            }
+        }
+    }
+    aside
+}
+
+/// Returns true for vowel letters, accented or not.
+fn is_vowel_letter(c: char) -> bool {
+    "aeiouyàâéèêëîïôûùü".contains(c)
+}
+
+/// Index of the syllable holding a Spanish word's written accent
+/// ("teléfono" -> 1), counting syllables by their vowel nuclei.
+///
+/// Unaccented `i`/`u` next to another vowel are glides, as in
+/// [`G2PConverter::syllabify`].
+fn spanish_accented_syllable(word: &str) -> Option<usize> {
+    let letters: Vec<char> = word.chars().collect();
+    let is_vowel = |i: usize| letters.get(i).is_some_and(|c| "aeiouáéíóúü".contains(*c));
+    let is_weak = |i: usize| "iuü".contains(letters[i]);
+
+    let mut nuclei = 0;
+    for (i, &c) in letters.iter().enumerate() {
+        if "áéíóú".contains(c) {
+            return Some(nuclei);
+        }
+        let glide = is_weak(i)
+            && ((is_vowel(i + 1) && letters[i + 1] != c) || (i > 0 && is_vowel(i - 1) && !is_weak(i - 1)));
+        if is_vowel(i) && !glide {
+            nuclei += 1;
+        }
+    }
+    None
+}
+
+/// Two-syllable English words whose first syllable is an unstressed
+/// prefix, so stress falls on the second ("about", "before").
+const UNSTRESSED_PREFIX_WORDS: &[&str] = &[
+    "about", "above", "abroad", "across", "again", "ago", "ahead", "alike", "alive", "alone", "along", "aloud",
+    "among", "amount", "apart", "around", "arise", "asleep", "aside", "attach", "attack", "attempt", "attend",
+    "avoid", "awake", "award", "aware", "away", "because", "become", "before", "began", "begin", "begun", "behalf",
+    "behave", "behind", "belief", "believe", "belong", "below", "beneath", "beside", "between", "beyond",
+];
+
+/// Returns true for English words opening with an unstressed prefix
+/// syllable (see [`UNSTRESSED_PREFIX_WORDS`]).
+fn has_unstressed_prefix(word: &str) -> bool {
+    UNSTRESSED_PREFIX_WORDS.contains(&word)
+}
+
+/// Returns true for English words ending in a syllable that is never
+/// stressed: "-er", "-le", a "-y" after a consonant ("butter", "little",
+/// "pretty", but not "today"), or a regular inflection ("needed").
+fn has_unstressed_ending(word: &str) -> bool {
+    if word.ends_with("er") || word.ends_with("le") || split_inflection(word).is_some() {
+        return true;
+    }
+    let mut letters = word.chars().rev();
+    letters.next() == Some('y') && letters.next().is_some_and(|c| c.is_alphabetic() && !is_vowel_letter(c))
+}
+
+/// Returns true for all-caps tokens of two or more letters ("HTTP", "NASA").
+fn is_initialism(token: &str) -> bool {
+    let letters: Vec<char> = token.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase())
+}
+
+/// Heuristic for all-caps words that read as words rather than letters:
+/// three or more letters, a vowel, an initial consonant cluster that can
+/// start a word and no run of four consonants ("STOP" and "WORLD" pass,
+/// "HTTP" and "FBI" do not).
+fn is_pronounceable(word: &str) -> bool {
+    let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    let is_vowel = |c: &char| "aeiouáéíóúü".contains(*c);
+    if letters.len() < 3 || !letters.iter().any(is_vowel) {
+        return false;
+    }
+
+    let onset: String = letters.iter().take_while(|c| !is_vowel(c)).collect();
+    let legal_onset = match onset.chars().count() {
+        0 | 1 => true,
+        2 => onset.starts_with('s') || onset.ends_with(['l', 'r', 'h', 'w']),
+        3 => onset.starts_with('s') && onset.ends_with(['l', 'r', 'w']),
+        _ => false,
+    };
+    let consonant_run = letters.windows(4).any(|w| w.iter().all(|c| !is_vowel(c)));
+    legal_onset && !consonant_run
+}
+
+/// Regular English inflectional suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Inflection {
+    /// "-ed"
+    Past,
+    /// "-s" or "-es"
+    Plural,
+}
+
+/// English words ending in "-s" that are not plurals ("atlas", "chaos").
+const UNINFLECTED_WORDS: &[&str] = &[
+    "alias", "atlas", "bias", "canvas", "chaos", "christmas", "cosmos", "ethos", "kudos", "pathos", "texas",
+];
+
+/// Split a regular English "-ed" or "-s" ending off a word, restoring the
+/// silent "e" a stem drops ("hoped" -> "hope", "boxes" -> "box").
+///
+/// Returns `None` for words that only look inflected ("bed", "bus", "this",
+/// "sacred", "atlas").
+fn split_inflection(word: &str) -> Option<(String, Inflection)> {
+    let has_vowel = |s: &str| s.chars().any(is_vowel_letter);
+    if UNINFLECTED_WORDS.contains(&word) {
+        return None;
+    }
+
+    if let Some(body) = word.strip_suffix("ed") {
+        // A stem needs a vowel and cannot end in consonant + "r" ("sacr-")
+        let letters: Vec<char> = body.chars().collect();
+        let consonant_r = matches!(letters.as_slice(), [.., c, 'r'] if !is_vowel_letter(*c));
+        if !has_vowel(body) || consonant_r {
+            return None;
+        }
+        let stem = if body.ends_with('e') || is_open_syllable_stem(body) {
+            format!("{}e", body)
+        } else {
+            body.to_string()
+        };
+        return Some((stem, Inflection::Past));
+    }
+
+    if ["ss", "us", "is"].iter().any(|s| word.ends_with(s)) {
+        return None;
+    }
+    let body = word.strip_suffix('s')?;
+    if body.chars().count() < 3 || !has_vowel(body) {
+        return None;
+    }
+    let stem = match body.strip_suffix('e') {
+        Some(base) if ["ss", "x", "zz", "ch", "sh"].iter().any(|s| base.ends_with(s)) => base,
+        _ => body,
+    };
+    Some((stem.to_string(), Inflection::Plural))
+}
+
+/// Returns true for one-syllable stems ending in a single vowel and
+/// consonant, which take a silent "e" before "-ed" ("hat" in "hated").
+fn is_open_syllable_stem(stem: &str) -> bool {
+    let letters: Vec<char> = stem.chars().collect();
+    let n = letters.len();
+    let vowel_groups = (0..n)
+        .filter(|&i| is_vowel_letter(letters[i]) && (i == 0 || !is_vowel_letter(letters[i - 1])))
+        .count();
+    n >= 2
+        && vowel_groups == 1
+        && !is_vowel_letter(letters[n - 1])
+        && !"wx".contains(letters[n - 1])
+        && is_vowel_letter(letters[n - 2])
+        && (n == 2 || !is_vowel_letter(letters[n - 3]))
+}
+
+/// Base letter of an accented Latin letter ("é" -> "e").
+fn latin_base_letter(c: char) -> Option<char> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+        'ç' | 'č' | 'ć' => 'c',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ł' => 'l',
+        'ñ' | 'ń' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+        'š' | 'ś' => 's',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ž' | 'ź' | 'ż' => 'z',
+        _ => return None,
+    })
+}
+
+/// English letter name as phonemes.
+fn english_letter_name(letter: char) -> Option<&'static str> {
+    Some(match letter {
+        'a' => "e",
+        'b' => "b i",
+        'c' => "s i",
+        'd' => "d i",
+        'e' => "i",
+        'f' => "E f",
+        'g' => "dZ i",
+        'h' => "e tS",
+        'i' => "aI",
+        'j' => "dZ e",
+        'k' => "k e",
+        'l' => "E l",
+        'm' => "E m",
+        'n' => "E n",
+        'o' => "o",
+        'p' => "p i",
+        'q' => "k j u",
+        'r' => "A r",
+        's' => "E s",
+        't' => "t i",
+        'u' => "j u",
+        'v' => "v i",
+        'w' => "d @ b @ l j u",
+        'x' => "E k s",
+        'y' => "w aI",
+        'z' => "z i",
+        _ => return None,
+    })
+}
+
+/// French letter name, spelled so the French rules pronounce it.
+fn french_letter_name(letter: char) -> Option<&'static str> {
+    Some(match letter {
+        'a' => "a",
+        'b' => "bé",
+        'c' => "cé",
+        'd' => "dé",
+        'e' => "eu",
+        'f' => "èf",
+        'g' => "gé",
+        'h' => "ache",
+        'i' => "i",
+        'j' => "ji",
+        'k' => "ka",
+        'l' => "èl",
+        'm' => "èm",
+        'n' => "èn",
+        'o' => "o",
+        'p' => "pé",
+        'q' => "ku",
+        'r' => "èr",
+        's' => "èsse",
+        't' => "té",
+        'u' => "u",
+        'v' => "vé",
+        'w' => "doublevé",
+        'x' => "ix",
+        'y' => "igrèc",
+        'z' => "zède",
+        _ => return None,
+    })
+}
+
+/// Spanish letter name, spelled so the Spanish rules pronounce it.
+fn spanish_letter_name(letter: char) -> Option<&'static str> {
+    Some(match letter {
+        'a' => "a",
+        'b' => "be",
+        'c' => "ce",
+        'd' => "de",
+        'e' => "e",
+        'f' => "efe",
+        'g' => "ge",
+        'h' => "hache",
+        'i' => "i",
+        'j' => "jota",
+        'k' => "ka",
+        'l' => "ele",
+        'm' => "eme",
+        'n' => "ene",
+        'ñ' => "eñe",
+        'o' => "o",
+        'p' => "pe",
+        'q' => "cu",
+        'r' => "erre",
+        's' => "ese",
+        't' => "te",
+        'u' => "u",
+        'v' => "uve",
+        'w' => "uvedoble",
+        'x' => "equis",
+        'y' => "ye",
+        'z' => "zeta",
+        _ => return None,
+    })
+}
+
+/// Parse an integer whose thousands may be grouped with `separator`
+/// ("1,000,000"). Groups after the first must be exactly three digits.
+fn parse_grouped_number(digits: &str, separator: char) -> Option<u64> {
+    let groups: Vec<&str> = digits.split(separator).collect();
+    let (first, rest) = groups.split_first()?;
+    let well_formed = if rest.is_empty() {
+        !first.is_empty()
+    } else {
+        (1..=3).contains(&first.len()) && rest.iter().all(|group| group.len() == 3)
+    };
+    if !well_formed || !groups.iter().all(|group| group.bytes().all(|b| b.is_ascii_digit())) {
+        return None;
+    }
+    let number: u64 = groups.concat().parse().ok()?;
+    (number <= MAX_SPOKEN_NUMBER).then_some(number)
+}
+
+/// English cardinal number as words ("forty two").
+fn english_number_words(number: u64) -> String {
+    const ONES: [&str; 20] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+
+    if number == 0 {
+        return ONES[0].to_string();
+    }
+    let mut words = Vec::new();
+    for (scale, name) in [(1_000_000_000, "billion"), (1_000_000, "million"), (1_000, "thousand"), (1, "")] {
+        let group = (number / scale % 1000) as usize;
+        if group == 0 {
+            continue;
+        }
+        if group >= 100 {
+            words.extend([ONES[group / 100], "hundred"]);
+        }
+        match group % 100 {
+            0 => {}
+            rest @ 1..=19 => words.push(ONES[rest]),
+            rest => {
+                words.push(TENS[rest / 10]);
+                if rest % 10 > 0 {
+                    words.push(ONES[rest % 10]);
+                }
+            }
+        }
+        if !name.is_empty() {
+            words.push(name);
+        }
+    }
+    words.join(" ")
+}
+
+/// English four-digit year as words ("nineteen ninety nine",
+/// "nineteen oh five", "two thousand five").
+fn english_year_words(year: u64) -> String {
+    let (century, rest) = (year / 100, year % 100);
+    if century % 10 == 0 && rest < 10 {
+        // 2000-2009 and the like read as plain numbers
+        return english_number_words(year);
+    }
+    let century = english_number_words(century);
+    match rest {
+        0 => format!("{} hundred", century),
+        1..=9 => format!("{} oh {}", century, english_number_words(rest)),
+        _ => format!("{} {}", century, english_number_words(rest)),
+    }
+}
+
+/// Spanish cardinal number as words ("cuarenta y dos").
+fn spanish_number_words(number: u64) -> String {
+    const UNITS: [&str; 30] = [
+        "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez",
+        "once", "doce", "trece", "catorce", "quince", "dieciséis", "diecisiete", "dieciocho",
+        "diecinueve", "veinte", "veintiuno", "veintidós", "veintitrés", "veinticuatro",
+        "veinticinco", "veintiséis", "veintisiete", "veintiocho", "veintinueve",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "", "treinta", "cuarenta", "cincuenta", "sesenta", "setenta", "ochenta", "noventa",
+    ];
+    const HUNDREDS: [&str; 10] = [
+        "", "ciento", "doscientos", "trescientos", "cuatrocientos", "quinientos", "seiscientos",
+        "setecientos", "ochocientos", "novecientos",
+    ];
+
+    fn below_thousand(number: usize) -> String {
+        if number == 100 {
+            return "cien".to_string();
+        }
+        let mut words = Vec::new();
+        if number >= 100 {
+            words.push(HUNDREDS[number / 100]);
+        }
+        match number % 100 {
+            0 => {}
+            rest @ 1..=29 => words.push(UNITS[rest]),
+            rest => {
+                words.push(TENS[rest / 10]);
+                if rest % 10 > 0 {
+                    words.extend(["y", UNITS[rest % 10]]);
+                }
+            }
+        }
+        words.join(" ")
+    }
+
+    // "uno" shortens before a noun: "veintiún mil", "un millón"
+    fn apocope(words: String) -> String {
+        if let Some(stem) = words.strip_suffix("veintiuno") {
+            format!("{}veintiún", stem)
+        } else if let Some(stem) = words.strip_suffix("uno") {
+            format!("{}un", stem)
+        } else {
+            words
+        }
+    }
+
+    fn below_million(number: usize) -> Vec<String> {
+        let mut words = Vec::new();
+        match number / 1000 {
+            0 => {}
+            1 => words.push("mil".to_string()),
+            thousands => words.push(format!("{} mil", apocope(below_thousand(thousands)))),
+        }
+        if number % 1000 != 0 {
+            words.push(below_thousand(number % 1000));
+        }
+        words
+    }
+
+    if number == 0 {
+        return UNITS[0].to_string();
+    }
+    let mut words = Vec::new();
+    match (number / 1_000_000) as usize {
+        0 => {}
+        1 => words.push("un millón".to_string()),
+        millions => words.push(format!("{} millones", apocope(below_million(millions).join(" ")))),
+    }
+    words.extend(below_million((number % 1_000_000) as usize));
+    words.join(" ")
+}
+
 /// Convert text to IPA phoneme representation.
 pub fn text_to_ipa(text: &str, language: &str) -> Result<String> {
+    text_to_ipa_with_options(text, language, &IpaOptions::default())
+}
+
+/// Convert text to IPA, optionally with syllable and stress marks.
+pub fn text_to_ipa_with_options(text: &str, language: &str, options: &IpaOptions) -> Result<String> {
     let converter = match language {
         "en" | "english" => G2PConverter::english(),
         "es" | "spanish" => G2PConverter::spanish(),
+        "fr" | "french" => G2PConverter::french(),
         _ => return Err(SynthesizerError::UnsupportedLanguage(language.to_string())),
     };
 
-    let phonemes = converter.convert(text)?;
-    
-    // Convert ASCII phonemes to IPA
-    let inventory = converter.inventory();
-    let ipa_result: Vec<String> = phonemes
-        .split_whitespace()
-        .map(|p| {
-            if p == "_" {
-                " ".to_string()
-            } else if let Some(phoneme) = inventory.get(p) {
-                phoneme.ipa.to_string()
-            } else {
-                p.to_string()
-            }
-        })
-        .collect();
-
-    Ok(ipa_result.join(""))
+    converter.to_ipa(text, options)
 }
 
 #[cfg(test)]
@@ -393,6 +2104,84 @@ mod tests {
         assert_eq!(result, "D @");
     }
 
+    #[test]
+    fn test_english_diphthongs() {
+        let g2p = G2PConverter::english();
+        assert_eq!(g2p.convert("my").unwrap(), "m aI");
+        assert_eq!(g2p.convert("fly").unwrap(), "f l aI");
+        assert_eq!(g2p.convert("night").unwrap(), "n aI t");
+        assert_eq!(g2p.convert("boy").unwrap(), "b OI");
+        // A final 'y' in a word with an earlier vowel stays /i/
+        assert_eq!(g2p.convert("happy").unwrap(), "h & p p i");
+    }
+
+    #[test]
+    fn test_rule_contexts() {
+        let ctx = RuleContext::parse("C?e$|#", false);
+        let letters = |w: &str| w.chars().collect::<Vec<_>>();
+        assert!(ctx.matches(&letters("ke")));
+        assert!(ctx.matches(&letters("e")));
+        assert!(ctx.matches(&letters("")));
+        assert!(!ctx.matches(&letters("ka")));
+        assert!(!ctx.matches(&letters("kke")));
+    }
+
+    #[test]
+    fn test_english_silent_e_and_soft_consonants() {
+        let g2p = G2PConverter::english();
+        // A final "e" one consonant away lengthens the vowel
+        assert_eq!(g2p.convert("make").unwrap(), "m e k");
+        assert_eq!(g2p.convert("time").unwrap(), "t aI m");
+        assert_eq!(g2p.convert("cute").unwrap(), "k u t");
+        // "c" and "g" soften before "y" as before "e" and "i"
+        assert_eq!(g2p.convert("fancy").unwrap(), "f & n s i");
+        assert_eq!(g2p.convert("gym").unwrap(), "dZ i m");
+        // Common words where the final "e" does not lengthen the vowel
+        assert_eq!(g2p.convert("come").unwrap(), "k @ m");
+        assert_eq!(g2p.convert("done").unwrap(), "d @ n");
+        assert_eq!(g2p.convert("give").unwrap(), "g I v");
+        assert_eq!(g2p.convert("there").unwrap(), "D E r");
+    }
+
+    #[test]
+    fn test_vowel_reduction() {
+        let mut g2p = G2PConverter::english();
+        assert_eq!(g2p.convert("fantastic").unwrap(), "f @ n t & s t I k");
+        assert_eq!(g2p.convert_marked("about").unwrap(), "@ ' b aU t");
+        assert_eq!(g2p.convert_marked("before").unwrap(), "b @ ' f o r");
+        // Words that merely look prefixed keep first-syllable stress
+        assert_eq!(g2p.convert_marked("atom").unwrap(), "' & . t A m");
+        assert_eq!(g2p.convert_marked("acid").unwrap(), "' & . s I d");
+        for word in ["agent", "apex", "bedroom", "beckon", "bevel"] {
+            assert!(g2p.convert_marked(word).unwrap().starts_with("' "), "{}", word);
+        }
+
+        // Words whose stress is only guessed keep their full vowels
+        assert_eq!(g2p.convert("banana").unwrap(), "b & n & n &");
+        assert_eq!(g2p.convert("hotel").unwrap(), "h A t E l");
+
+        g2p.set_vowel_reduction(false);
+        assert_eq!(g2p.convert("fantastic").unwrap(), "f & n t & s t I k");
+    }
+
+    #[test]
+    fn test_american_flap() {
+        let mut g2p = G2PConverter::english();
+        assert_eq!(g2p.convert("city").unwrap(), "s I 4 i");
+        assert_eq!(g2p.convert("better").unwrap(), "b E 4 @ r");
+        // Not before a stressed vowel or after a consonant
+        assert_eq!(g2p.convert("attack").unwrap(), "@ t t & k");
+        assert_eq!(g2p.convert("after").unwrap(), "& f t @ r");
+        assert_eq!(g2p.convert("at").unwrap(), "& t");
+        // Nor where the stress is only a guess
+        assert_eq!(g2p.convert("today").unwrap(), "t A d e");
+        assert!(!g2p.convert("potato").unwrap().contains('4'));
+        assert!(!g2p.convert("hotel").unwrap().contains('4'));
+
+        g2p.set_flapping(false);
+        assert_eq!(g2p.convert("city").unwrap(), "s I t i");
+    }
+
     #[test]
     fn test_spanish_g2p_basic() {
         let g2p = G2PConverter::spanish();
@@ -400,6 +2189,21 @@ mod tests {
         assert!(!result.is_empty());
     }
 
+    #[test]
+    fn test_spanish_variants() {
+        let castilian = G2PConverter::spanish();
+        assert_eq!(castilian.convert("cielo").unwrap(), "T i e l o");
+        assert_eq!(castilian.convert("calle").unwrap(), "k a L e");
+
+        let mut g2p = G2PConverter::spanish_with_variant(SpanishVariant::LatinAmerican);
+        assert_eq!(g2p.convert("cielo").unwrap(), "s i e l o");
+        assert_eq!(g2p.convert("calle").unwrap(), "k a j e");
+        assert_eq!(g2p.convert("zapato").unwrap(), "s a p a t o");
+
+        g2p.set_spanish_variant(SpanishVariant::Castilian);
+        assert_eq!(g2p.convert("zapato").unwrap(), "T a p a t o");
+    }
+
     #[test]
     fn test_spanish_vowels() {
         let g2p = G2PConverter::spanish();
@@ -407,15 +2211,357 @@ mod tests {
         assert_eq!(result, "a e i o u");
     }
 
+    #[test]
+    fn test_secondary_stress() {
+        let g2p = G2PConverter::english();
+        let marked = g2p.convert_marked("organization").unwrap();
+        let tokens: Vec<&str> = marked.split_whitespace().collect();
+        assert_eq!(tokens.iter().filter(|t| **t == "'").count(), 1);
+        assert!(tokens.contains(&","));
+
+        let options = IpaOptions { stress_marks: true, ..IpaOptions::default() };
+        let ipa = text_to_ipa_with_options("organization", "en", &options).unwrap();
+        assert!(ipa.contains('ˌ') && ipa.contains('ˈ'));
+    }
+
+    #[test]
+    fn test_caps_policy() {
+        let mut g2p = G2PConverter::english();
+        let stop = g2p.convert("stop").unwrap();
+        let http = g2p.convert("HTTP").unwrap();
+        assert_ne!(g2p.convert("STOP").unwrap(), stop);
+
+        g2p.set_caps_policy(CapsPolicy::Shout);
+        assert_eq!(g2p.convert_marked("STOP").unwrap(), format!("! {}", stop));
+        assert_eq!(g2p.convert("STOP").unwrap(), stop);
+        assert!(!g2p.convert_marked("HTTP").unwrap().starts_with('!'));
+        assert_eq!(g2p.convert("HTTP").unwrap(), http);
+
+        g2p.set_caps_policy(CapsPolicy::Ignore);
+        assert_eq!(g2p.convert_marked("STOP").unwrap(), stop);
+        assert_eq!(g2p.convert("HTTP").unwrap(), g2p.convert("http").unwrap());
+    }
+
+    #[test]
+    fn test_quote_detection() {
+        let mut g2p = G2PConverter::english();
+        let text = "she said \u{201C}come here\u{201D} today";
+        let quoted_words = |phonemes: &str| phonemes.split(" _ ").filter(|w| w.starts_with('"')).count();
+        assert_eq!(quoted_words(&g2p.convert_marked(text).unwrap()), 0);
+
+        g2p.set_quote_detection(true);
+        let marked = g2p.convert_marked(text).unwrap();
+        assert_eq!(quoted_words(&marked), 2);
+        assert!(marked.split(" _ ").nth(2).unwrap().starts_with('"'));
+        assert!(!marked.split(" _ ").nth(4).unwrap().starts_with('"'));
+        assert!(!g2p.convert(text).unwrap().contains('"'));
+
+        // Straight quotes, a lone closing mark and an unmatched opening one
+        assert_eq!(quoted_words(&g2p.convert_marked("he said \"stop\" twice").unwrap()), 1);
+        assert_eq!(quoted_words(&g2p.convert_marked("he said \" stop now \"").unwrap()), 2);
+        assert_eq!(quoted_words(&g2p.convert_marked("he said \"stop now").unwrap()), 0);
+    }
+
+    #[test]
+    fn test_parenthetical_detection() {
+        let mut g2p = G2PConverter::english();
+        // Indices of the words that carry the parenthetical mark
+        let asides = |phonemes: &str| -> Vec<usize> {
+            let tokens: Vec<&str> = phonemes.split_whitespace().collect();
+            let words: Vec<&[&str]> = tokens.split(|t| *t == "_").filter(|w| !w.is_empty()).collect();
+            (0..words.len()).filter(|&i| words[i][0] == "(").collect()
+        };
+        let text = "I was, you know, tired (as usual) today.";
+        assert!(asides(&g2p.convert_marked(text).unwrap()).is_empty());
+
+        g2p.set_parenthetical_detection(true);
+        let marked = g2p.convert_marked(text).unwrap();
+        assert_eq!(asides(&marked), [2, 3, 5, 6]);
+        assert!(marked.contains("_ _ ("));
+        assert!(!g2p.convert(text).unwrap().contains('('));
+
+        // Nested parentheses belong to the outer phrase; unmatched ones and
+        // a comma phrase that ends the sentence mark nothing
+        assert_eq!(asides(&g2p.convert_marked("go (now (really) please) home").unwrap()), [1, 2, 3]);
+        assert!(asides(&g2p.convert_marked("go (now please home").unwrap()).is_empty());
+        assert!(asides(&g2p.convert_marked("I was tired, you know.").unwrap()).is_empty());
+        // The aside is "well", not the clause "and then"
+        let marked = g2p.convert_marked("so, in the end we left, and then, well, nothing").unwrap();
+        assert_eq!(asides(&marked), [8]);
+        // List items are not asides
+        assert!(asides(&g2p.convert_marked("I like apples, pears, and plums").unwrap()).is_empty());
+        assert!(asides(&g2p.convert_marked("red, green, blue, and gold").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_foreign_text_policy() {
+        let mut g2p = G2PConverter::english();
+        let gaps = |phonemes: &str| phonemes.split_whitespace().filter(|p| *p == "_").count();
+
+        let skipped = g2p.convert_verbose("visit 東京 today").unwrap();
+        assert_eq!(gaps(&skipped.phonemes), 1);
+        assert_eq!(skipped.dropped, vec!['東', '京']);
+
+        g2p.set_foreign_text_policy(ForeignTextPolicy::Pause);
+        let paused = g2p.convert_verbose("visit 東京 today").unwrap();
+        assert_eq!(gaps(&paused.phonemes), FOREIGN_TEXT_PAUSE);
+        assert_eq!(paused.dropped, vec!['東', '京']);
+        assert_eq!(g2p.convert("café").unwrap(), g2p.convert("caf").unwrap());
+
+        g2p.set_foreign_text_policy(ForeignTextPolicy::Transliterate);
+        assert_eq!(g2p.convert("café").unwrap(), g2p.convert("cafe").unwrap());
+        assert_eq!(gaps(&g2p.convert("visit 東京 today").unwrap()), FOREIGN_TEXT_PAUSE);
+    }
+
+    #[test]
+    fn test_grouped_numbers() {
+        let english = G2PConverter::english();
+        let read_as = |g2p: &G2PConverter, text: &str, words: &str| {
+            assert_eq!(g2p.convert(text).unwrap(), g2p.convert(words).unwrap(), "{}", text);
+        };
+        read_as(&english, "1,000", "one thousand");
+        read_as(&english, "1,000,000", "one million");
+        read_as(&english, "It costs 2,500,042.", "it costs two million five hundred thousand forty two");
+        // Not clearly grouped thousands: each part read on its own
+        read_as(&english, "1,5", "one five");
+        read_as(&english, "12,34", "twelve thirty four");
+        read_as(&english, "1,2,3", "one two three");
+        read_as(&english, "1,5.3", "one five three");
+        // The other separator is a decimal point
+        read_as(&english, "1.000", "one point zero zero zero");
+
+        let spanish = G2PConverter::spanish();
+        read_as(&spanish, "1.000", "mil");
+        read_as(&spanish, "21.000", "veintiún mil");
+        read_as(&spanish, "1.000.000", "un millón");
+        read_as(&spanish, "2.000.115", "dos millones ciento quince");
+        read_as(&spanish, "1,000", "uno coma cero cero cero");
+    }
+
+    #[test]
+    fn test_number_expansion() {
+        let english = G2PConverter::english();
+        let read_as = |g2p: &G2PConverter, text: &str, words: &str| {
+            assert_eq!(g2p.convert(text).unwrap(), g2p.convert(words).unwrap(), "{}", text);
+        };
+        read_as(&english, "I have 42 cats", "I have forty two cats");
+        read_as(&english, "-7", "minus seven");
+        read_as(&english, "3000000", "three million");
+        read_as(&english, "1999", "nineteen ninety nine");
+        read_as(&english, "1905", "nineteen oh five");
+        read_as(&english, "1900", "nineteen hundred");
+        read_as(&english, "2005", "two thousand five");
+        read_as(&english, "1,999", "one thousand nine hundred ninety nine");
+        read_as(&english, "pi is 3.14", "pi is three point one four");
+        read_as(&english, "1.5", "one point five");
+        read_as(&english, "$5.50.", "$five point five zero.");
+        read_as(&english, "-0.25", "minus zero point two five");
+        read_as(&english, "1,000.5", "one thousand point five");
+
+        let spanish = G2PConverter::spanish();
+        read_as(&spanish, "tengo 42 gatos", "tengo cuarenta y dos gatos");
+        read_as(&spanish, "-15", "menos quince");
+        read_as(&spanish, "1999", "mil novecientos noventa y nueve");
+        read_as(&spanish, "100", "cien");
+        read_as(&spanish, "101", "ciento uno");
+        read_as(&spanish, "3,14", "tres coma uno cuatro");
+        read_as(&spanish, "1.000,5", "mil coma cinco");
+    }
+
+    #[test]
+    fn test_convert_reports_dropped_characters() {
+        let mut g2p = G2PConverter::english();
+        let report = g2p.convert_verbose("hello мир").unwrap();
+        assert_eq!(report.phonemes, g2p.convert("hello").unwrap());
+        assert_eq!(report.dropped, vec!['м', 'и', 'р']);
+        assert!(g2p.convert_verbose("don't stop").unwrap().dropped.is_empty());
+        // Digits stripped by normalization, but not those read as numbers
+        assert_eq!(g2p.convert_verbose("room 2b, 3.14").unwrap().dropped, vec!['2']);
+
+        g2p.set_strict(true);
+        assert!(matches!(g2p.convert("мир"), Err(SynthesizerError::PhonemeError(_))));
+        assert!(matches!(g2p.convert("x2"), Err(SynthesizerError::PhonemeError(_))));
+        assert!(g2p.convert("hello").is_ok());
+        assert!(g2p.convert("pi is 3.14").is_ok());
+    }
+
+    #[test]
+    fn test_spanish_long_accented_word() {
+        let g2p = G2PConverter::spanish();
+        assert_eq!(
+            g2p.convert("electroencefalografía").unwrap(),
+            "e l e k t r o e n T e f a l o g r a f i a"
+        );
+        assert_eq!(g2p.convert("cigüeña").unwrap(), "T i g u e J a");
+    }
+
     #[test]
     fn test_text_to_ipa_english() {
         let result = text_to_ipa("hello", "en").unwrap();
         assert!(!result.is_empty());
     }
 
+    #[test]
+    fn test_french_g2p() {
+        let g2p = G2PConverter::french();
+        assert_eq!(g2p.convert("bonjour").unwrap(), "b O~ Z u R");
+        assert_eq!(g2p.convert("château").unwrap(), "S a t o");
+        assert_eq!(g2p.convert("maison").unwrap(), "m E z O~");
+        assert_eq!(g2p.convert("français").unwrap(), "f R A~ s E");
+        assert_eq!(g2p.convert("petit").unwrap(), "p @ t i");
+        assert_eq!(g2p.convert("deux").unwrap(), "d 2");
+        // n before a vowel or doubled is not nasalized
+        assert_eq!(g2p.convert("anne").unwrap(), "a n");
+        assert_eq!(text_to_ipa("bonjour", "fr").unwrap(), "bɔ̃ʒuʁ");
+    }
+
+    #[test]
+    fn test_load_exceptions_from_str() {
+        let mut g2p = G2PConverter::english();
+        let lexicon = "# medical terms\nsepsis\ts E p s I s\n\nAorta\te O r t @\n";
+        assert_eq!(g2p.load_exceptions_from_str(lexicon).unwrap(), 2);
+        assert_eq!(g2p.convert("Sepsis").unwrap(), "s E p s I s");
+        assert_eq!(g2p.convert("aorta").unwrap(), "e O r t @");
+
+        g2p.add_exception("hello", "h @ l o");
+        assert_eq!(g2p.convert("hello").unwrap(), "h @ l o");
+
+        assert!(matches!(
+            g2p.load_exceptions_from_str("femur f i m @ r"),
+            Err(SynthesizerError::PhonemeError(_))
+        ));
+        assert!(matches!(
+            g2p.load_exceptions_from_str("tibia\tt I b i q\nulna\t@ l n @"),
+            Err(SynthesizerError::InvalidPhoneme(_))
+        ));
+        // A failed load adds nothing
+        assert!(!g2p.exceptions.contains_key("ulna"));
+    }
+
     #[test]
     fn test_unsupported_language() {
-        let result = text_to_ipa("test", "fr");
+        let result = text_to_ipa("test", "de");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_english_possessive() {
+        let g2p = G2PConverter::english();
+        assert!(g2p.convert("dog's").unwrap().ends_with(" z"));
+        assert!(g2p.convert("cat's").unwrap().ends_with(" s"));
+        assert!(g2p.convert("James's").unwrap().ends_with(" I z"));
+        assert_eq!(g2p.convert("dogs'").unwrap(), g2p.convert("dogs").unwrap());
+    }
+
+    #[test]
+    fn test_english_inflections() {
+        let g2p = G2PConverter::english();
+        assert_eq!(g2p.convert("kissed").unwrap(), "k I s s t");
+        assert_eq!(g2p.convert("buzzed").unwrap(), "b @ z z d");
+        assert_eq!(g2p.convert("needed").unwrap(), "n i 4 I d");
+        assert_eq!(g2p.convert("hoped").unwrap(), "h o p t");
+        assert_eq!(g2p.convert("boxes").unwrap(), "b A k s I z");
+        assert_eq!(g2p.convert("boxed").unwrap(), "b A k s t");
+        assert_eq!(g2p.convert("dogs").unwrap(), "d A g z");
+        assert_eq!(g2p.convert("cats").unwrap(), "k & t s");
+        // Not inflections
+        assert_eq!(g2p.convert("bed").unwrap(), "b E d");
+        assert_eq!(g2p.convert("bus").unwrap(), "b @ s");
+        assert_eq!(g2p.convert("sacred").unwrap(), "s & k r E d");
+        assert_eq!(g2p.convert("atlas").unwrap(), "& t l & s");
+        assert_eq!(g2p.convert("chaos").unwrap(), "k e A s");
+        assert_eq!(g2p.convert("bias").unwrap(), "b aI @ s");
+        assert_eq!(g2p.convert("united").unwrap(), "j u n aI t I d");
+    }
+
+    #[test]
+    fn test_line_and_paragraph_pauses() {
+        let g2p = G2PConverter::english();
+        assert_eq!(g2p.convert("a\nthe").unwrap(), "@ _ _ D @");
+        assert_eq!(g2p.convert("a\n\n the").unwrap(), "@ _ _ _ _ _ D @");
+        assert_eq!(g2p.convert("a the").unwrap(), "@ _ D @");
+
+        let g2p = G2PConverter::english().with_break_pauses(1, 3);
+        assert_eq!(g2p.convert("a\nthe\n\na").unwrap(), "@ _ D @ _ _ _ @");
+        assert_eq!(text_to_ipa("a\n\nthe", "en").unwrap(), "ə ðə");
+    }
+
+    #[test]
+    fn test_syllabify() {
+        let g2p = G2PConverter::spanish();
+        let phonemes = g2p.convert("ventana abrir").unwrap();
+        assert_eq!(g2p.syllabify(&phonemes), ["b e n", "t a", "n a", "a", "b r i r"]);
+        assert_eq!(g2p.syllabify("' k a . s a |"), ["k a", "s a"]);
+
+        let g2p = G2PConverter::english();
+        assert_eq!(g2p.syllabify("E k s t r @"), ["E k", "s t r @"]);
+        assert!(g2p.syllabify("").is_empty());
+    }
+
+    #[test]
+    fn test_convert_marked_syllables() {
+        let g2p = G2PConverter::spanish();
+        assert_eq!(g2p.convert_marked("casa").unwrap(), "' k a . s a");
+        assert_eq!(g2p.convert_marked("bueno").unwrap(), "' b u e . n o");
+        // Written accents override the default stress, and glides stay
+        // with their nucleus
+        assert_eq!(g2p.convert_marked("telefono").unwrap(), ", t e . l e ' f o . n o");
+        assert_eq!(g2p.convert_marked("teléfono").unwrap(), "t e ' l e . f o . n o");
+        assert_eq!(g2p.convert_marked("canción").unwrap(), "k a n ' T i o n");
+        assert_eq!(g2p.convert_marked("papel").unwrap(), "p a ' p e l");
+        assert_eq!(g2p.convert_marked("sol").unwrap(), "s o l");
+    }
+
+    #[test]
+    fn test_convert_marked_sentence_ends() {
+        let g2p = G2PConverter::english();
+        let marked = g2p.convert_marked("Is it? It is. \"Yes!\" no").unwrap();
+        let marks: Vec<&str> = marked.split_whitespace().filter(|t| matches!(*t, "?" | "|")).collect();
+        assert_eq!(marks, ["?", "|", "|"]);
+        assert!(!g2p.convert("Is it?").unwrap().contains('?'));
+        assert_eq!(g2p.to_ipa("it is.", &IpaOptions::default()).unwrap(), text_to_ipa("it is", "en").unwrap());
+    }
+
+    #[test]
+    fn test_text_to_ipa_with_marks() {
+        let stress = IpaOptions {
+            stress_marks: true,
+            ..Default::default()
+        };
+        assert_eq!(text_to_ipa_with_options("casa", "es", &stress).unwrap(), "ˈkasa");
+
+        let syllables = IpaOptions {
+            syllable_marks: true,
+            word_separator: '|',
+            ..Default::default()
+        };
+        assert_eq!(text_to_ipa_with_options("casa blanca", "es", &syllables).unwrap(), "ka.sa|blan.ka");
+
+        let both = IpaOptions {
+            syllable_marks: true,
+            stress_marks: true,
+            ..Default::default()
+        };
+        assert_eq!(text_to_ipa_with_options("hello", "en", &both).unwrap(), "ˈhɛ.loʊ");
+        assert_eq!(
+            text_to_ipa_with_options("hello", "en", &IpaOptions::default()).unwrap(),
+            text_to_ipa("hello", "en").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_initialisms() {
+        let mut g2p = G2PConverter::english();
+        assert_eq!(g2p.convert("SQL").unwrap(), "s i k w @ l");
+        assert_eq!(g2p.convert("HTTP").unwrap(), "e tS t i t i p i");
+        assert_eq!(g2p.convert("GIF").unwrap(), g2p.convert("gif").unwrap());
+        assert_ne!(g2p.convert("http").unwrap(), g2p.convert("HTTP").unwrap());
+
+        g2p.add_spoken_initialism("GIF", Some("dZ I f"));
+        assert_eq!(g2p.convert("GIF").unwrap(), "dZ I f");
+
+        let g2p = G2PConverter::spanish();
+        assert_eq!(g2p.convert("ONU").unwrap(), "o n u");
+        assert_eq!(g2p.convert("DF").unwrap(), "d e e f e");
+    }
 }
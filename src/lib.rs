@@ -4,7 +4,7 @@
 //!
 //! Parlador is a custom speech synthesis library that uses formant synthesis
 //! to generate speech audio from text. It provides a high-level API for
-//! text-to-speech synthesis with support for English and Spanish, and is
+//! text-to-speech synthesis with support for English, Spanish and French, and is
 //! designed to be compatible with external TTS models like Kokoro through
 //! phoneme generation.
 //!
@@ -12,7 +12,7 @@
 //!
 //! - **Cross-platform**: Pure Rust implementation, works on Linux, macOS, and Windows
 //! - **No external dependencies**: Self-contained formant synthesis engine
-//! - **Multiple languages**: Support for English and Spanish
+//! - **Multiple languages**: Support for English, Spanish and French
 //! - **Voice customization**: Adjust rate, pitch, volume, and voice variants
 //! - **Phoneme generation**: Compatible with TTS models like Kokoro
 //! - **Audio synthesis**: Get raw audio data (16-bit PCM) for further processing
@@ -75,15 +75,29 @@ mod error;
 mod formant;
 mod g2p;
 mod phoneme;
+#[cfg(feature = "playback")]
+mod playback;
 mod synthesizer;
+#[cfg(test)]
+mod test_util;
 mod voice;
 
 pub use error::{Result, SynthesizerError};
-pub use formant::{AudioOutput, SynthesisConfig, SAMPLE_RATE};
-pub use g2p::{text_to_ipa, G2PConverter};
-pub use phoneme::{FormantValues, Phoneme, PhonemeCategory, PhonemeInventory};
+pub use formant::{
+    AudioOutput, Endianness, PhonemeTiming, SynthesisConfig, MAX_SAMPLE_RATE, MIN_SAMPLE_RATE,
+    SAMPLE_RATE,
+};
+pub use g2p::{
+    text_to_ipa, text_to_ipa_with_options, CapsPolicy, ConversionReport, ForeignTextPolicy,
+    G2PConverter, IpaOptions, SpanishVariant,
+};
+pub use phoneme::{FormantValues, Phoneme, PhonemeCategory, PhonemeInventory, StressLevel, Viseme};
 pub use synthesizer::{
-    espeak_initialize, espeak_set_voice_by_name, espeak_synth, espeak_terminate,
-    espeak_text_to_phonemes, AudioOutputType, PhonemeFormat, PhonemeResult, Synthesizer,
+    espeak_get_parameter, espeak_initialize, espeak_set_parameter, espeak_set_voice_by_name, espeak_synth,
+    espeak_synth_with_callback, espeak_terminate, espeak_text_to_phonemes, AudioOutputType, EspeakEvent,
+    EspeakParameter, FitPolicy, PhonemeFormat, PhonemeResult, SynthesisSession, Synthesizer, VisemeTrack,
+};
+pub use voice::{
+    hz_to_midi, midi_to_hz, semitone_ratio, EnglishAccent, IntonationProfile, Language, VoiceConfig, VoiceRegistry,
+    VoiceVariant,
 };
-pub use voice::{Language, VoiceConfig, VoiceVariant};
@@ -1,10 +1,12 @@
 //! Phoneme definitions and mappings for the speech synthesizer.
 //!
-//! This module defines the phoneme inventory for English and Spanish,
+//! This module defines the phoneme inventories for English, Spanish and French,
 //! with mappings to IPA (International Phonetic Alphabet) and acoustic
 //! parameters for formant synthesis.
 
+use crate::formant::SAMPLE_RATE;
 use std::collections::HashMap;
+use std::f32::consts::PI;
 
 /// A phoneme with its acoustic properties for formant synthesis.
 #[derive(Debug, Clone)]
@@ -19,6 +21,8 @@ pub struct Phoneme {
     pub duration_ms: u32,
     /// Formant frequencies (F1, F2, F3) in Hz for vowels.
     pub formants: Option<FormantValues>,
+    /// Final formant targets for diphthongs, which glide from `formants`.
+    pub formants_end: Option<FormantValues>,
     /// Voicing information.
     pub voiced: bool,
 }
@@ -57,6 +61,19 @@ impl FormantValues {
     pub const fn with_bandwidths(f1: f32, f2: f32, f3: f32, b1: f32, b2: f32, b3: f32) -> Self {
         Self { f1, f2, f3, b1, b2, b3 }
     }
+
+    /// Linearly interpolates towards `other` (`t` = 0.0 returns `self`).
+    pub fn lerp(&self, other: &FormantValues, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            f1: mix(self.f1, other.f1),
+            f2: mix(self.f2, other.f2),
+            f3: mix(self.f3, other.f3),
+            b1: mix(self.b1, other.b1),
+            b2: mix(self.b2, other.b2),
+            b3: mix(self.b3, other.b3),
+        }
+    }
 }
 
 /// Categories of phonemes.
@@ -84,6 +101,123 @@ pub enum PhonemeCategory {
     Silence,
 }
 
+/// Mouth shape (viseme) shown while a phoneme is spoken, for facial animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Viseme {
+    /// Lips closed, as for /p/, /b/ and /m/.
+    Closed,
+    /// Jaw open, as for /ɑ/.
+    Open,
+    /// Lips rounded, as for /u/ and /w/.
+    Rounded,
+    /// Lips spread, as for /i/ and /s/.
+    Spread,
+    /// Lower lip against the upper teeth, as for /f/ and /v/.
+    LabioDental,
+    /// Tongue between the teeth, as for /θ/ and /ð/.
+    Dental,
+    /// Relaxed mouth, for silence and sounds without a distinct lip shape.
+    Neutral,
+}
+
+/// F1 (Hz) from which a vowel is shown with an open jaw.
+const OPEN_VOWEL_F1: f32 = 650.0;
+/// F2 (Hz) below which a vowel is back, and shown rounded.
+const BACK_VOWEL_F2: f32 = 1100.0;
+/// F3 (Hz) below which a vowel is shown rounded; lip rounding lowers F3,
+/// which also catches front rounded vowels such as French /y/.
+const ROUNDED_VOWEL_F3: f32 = 2350.0;
+/// F2 (Hz) from which a vowel is front, and shown spread.
+const FRONT_VOWEL_F2: f32 = 1800.0;
+
+impl Phoneme {
+    /// Returns the mouth shape for this phoneme.
+    ///
+    /// Vowels are classed by their (starting) formants: a high F1 opens the
+    /// jaw, a low F2 or F3 rounds the lips and a high F2 spreads them.
+    /// Stops and nasals close the mouth.
+    #[must_use]
+    pub fn viseme(&self) -> Viseme {
+        match self.category {
+            PhonemeCategory::Vowel | PhonemeCategory::Diphthong => match &self.formants {
+                Some(f) if f.f1 >= OPEN_VOWEL_F1 => Viseme::Open,
+                Some(f) if f.f2 < BACK_VOWEL_F2 || f.f3 < ROUNDED_VOWEL_F3 => Viseme::Rounded,
+                Some(f) if f.f2 >= FRONT_VOWEL_F2 => Viseme::Spread,
+                _ => Viseme::Neutral,
+            },
+            PhonemeCategory::Plosive | PhonemeCategory::Nasal => Viseme::Closed,
+            PhonemeCategory::Silence => Viseme::Neutral,
+            _ => match self.symbol {
+                "f" | "v" => Viseme::LabioDental,
+                "T" | "D" => Viseme::Dental,
+                "s" | "z" => Viseme::Spread,
+                "S" | "Z" | "tS" | "dZ" | "w" | "H" => Viseme::Rounded,
+                _ => Viseme::Neutral,
+            },
+        }
+    }
+}
+
+/// Token placed before an emphasized (shouted) word in marked phoneme
+/// sequences.
+pub(crate) const EMPHASIS_MARK: &str = "!";
+
+/// Token placed before each word of a quoted span in marked phoneme
+/// sequences; the word is spoken at a slightly raised pitch.
+pub(crate) const QUOTE_MARK: &str = "\"";
+
+/// Token placed before each word of a parenthetical phrase in marked
+/// phoneme sequences; the word is spoken lower and faster.
+pub(crate) const PARENTHETICAL_MARK: &str = "(";
+
+/// Token ending a question in marked phoneme sequences; the sentence is
+/// spoken with a final rise.
+pub(crate) const QUESTION_MARK: &str = "?";
+
+/// Token ending any other sentence in marked phoneme sequences; the
+/// sentence is spoken with a falling pitch.
+pub(crate) const STATEMENT_MARK: &str = "|";
+
+/// Stress level of a syllable.
+///
+/// In marked phoneme sequences each level is written as a token before the
+/// syllable it applies to: `'` (primary), `,` (secondary) or `.` (an
+/// unstressed syllable boundary).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default)]
+pub enum StressLevel {
+    /// No stress.
+    #[default]
+    Unstressed,
+    /// Secondary stress ("or" in "organization").
+    Secondary,
+    /// Primary stress ("za" in "organization").
+    Primary,
+}
+
+impl StressLevel {
+    /// Returns the token that marks a syllable with this stress level.
+    #[must_use]
+    pub fn mark(&self) -> &'static str {
+        match self {
+            StressLevel::Unstressed => ".",
+            StressLevel::Secondary => ",",
+            StressLevel::Primary => "'",
+        }
+    }
+
+    /// Parses a syllable mark token.
+    #[must_use]
+    pub fn from_mark(token: &str) -> Option<Self> {
+        match token {
+            "." => Some(StressLevel::Unstressed),
+            "," => Some(StressLevel::Secondary),
+            "'" => Some(StressLevel::Primary),
+            _ => None,
+        }
+    }
+}
+
 /// Phoneme inventory for a language.
 #[derive(Debug, Clone)]
 pub struct PhonemeInventory {
@@ -106,6 +240,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 120,
             formants: Some(FormantValues::new(270.0, 2290.0, 3010.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("I", Phoneme {
@@ -114,6 +249,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(390.0, 1990.0, 2550.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("e", Phoneme {
@@ -122,6 +258,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Diphthong,
             duration_ms: 140,
             formants: Some(FormantValues::new(530.0, 1840.0, 2480.0)),
+            formants_end: Some(FormantValues::new(390.0, 2100.0, 2600.0)),
             voiced: true,
         });
         phonemes.insert("E", Phoneme {
@@ -130,6 +267,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(610.0, 1900.0, 2530.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("&", Phoneme {
@@ -138,6 +276,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 120,
             formants: Some(FormantValues::new(660.0, 1720.0, 2410.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("A", Phoneme {
@@ -146,6 +285,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 130,
             formants: Some(FormantValues::new(730.0, 1090.0, 2440.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("O", Phoneme {
@@ -154,6 +294,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 120,
             formants: Some(FormantValues::new(570.0, 840.0, 2410.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("o", Phoneme {
@@ -162,6 +303,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Diphthong,
             duration_ms: 140,
             formants: Some(FormantValues::new(450.0, 1030.0, 2380.0)),
+            formants_end: Some(FormantValues::new(370.0, 900.0, 2300.0)),
             voiced: true,
         });
         phonemes.insert("U", Phoneme {
@@ -170,6 +312,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(440.0, 1020.0, 2240.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("u", Phoneme {
@@ -178,6 +321,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 120,
             formants: Some(FormantValues::new(300.0, 870.0, 2240.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("@", Phoneme {
@@ -186,6 +330,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 80,
             formants: Some(FormantValues::new(500.0, 1500.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("3", Phoneme {
@@ -194,6 +339,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 120,
             formants: Some(FormantValues::new(580.0, 1380.0, 2530.0)),
+            formants_end: None,
             voiced: true,
         });
 
@@ -204,6 +350,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Diphthong,
             duration_ms: 180,
             formants: Some(FormantValues::new(700.0, 1200.0, 2600.0)),
+            formants_end: Some(FormantValues::new(400.0, 2000.0, 2600.0)),
             voiced: true,
         });
         phonemes.insert("aU", Phoneme {
@@ -212,6 +359,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Diphthong,
             duration_ms: 180,
             formants: Some(FormantValues::new(700.0, 1000.0, 2400.0)),
+            formants_end: Some(FormantValues::new(450.0, 900.0, 2350.0)),
             voiced: true,
         });
         phonemes.insert("OI", Phoneme {
@@ -220,6 +368,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Diphthong,
             duration_ms: 180,
             formants: Some(FormantValues::new(570.0, 1000.0, 2500.0)),
+            formants_end: Some(FormantValues::new(400.0, 1950.0, 2550.0)),
             voiced: true,
         });
 
@@ -230,6 +379,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("b", Phoneme {
@@ -238,6 +388,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("t", Phoneme {
@@ -246,6 +397,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("d", Phoneme {
@@ -254,6 +406,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("k", Phoneme {
@@ -262,6 +415,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("g", Phoneme {
@@ -270,6 +424,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
 
@@ -280,6 +435,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 80,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("v", Phoneme {
@@ -288,6 +444,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 80,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("T", Phoneme {
@@ -296,6 +453,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 80,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("D", Phoneme {
@@ -304,6 +462,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 80,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("s", Phoneme {
@@ -312,6 +471,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 90,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("z", Phoneme {
@@ -320,6 +480,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 90,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("S", Phoneme {
@@ -328,6 +489,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 100,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("Z", Phoneme {
@@ -336,6 +498,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 100,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("h", Phoneme {
@@ -344,6 +507,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
 
@@ -354,6 +518,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Affricate,
             duration_ms: 110,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("dZ", Phoneme {
@@ -362,6 +527,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Affricate,
             duration_ms: 110,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
 
@@ -372,6 +538,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Nasal,
             duration_ms: 80,
             formants: Some(FormantValues::new(300.0, 1000.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("n", Phoneme {
@@ -380,6 +547,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Nasal,
             duration_ms: 80,
             formants: Some(FormantValues::new(300.0, 1500.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("N", Phoneme {
@@ -388,6 +556,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Nasal,
             duration_ms: 80,
             formants: Some(FormantValues::new(300.0, 2000.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
 
@@ -398,6 +567,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Lateral,
             duration_ms: 70,
             formants: Some(FormantValues::new(350.0, 1100.0, 2700.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("r", Phoneme {
@@ -406,6 +576,17 @@ impl PhonemeInventory {
             category: PhonemeCategory::Rhotic,
             duration_ms: 70,
             formants: Some(FormantValues::new(350.0, 1300.0, 1700.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        // American flap ("butter", "city"): a quick tap with no closure
+        phonemes.insert("4", Phoneme {
+            symbol: "4",
+            ipa: "ɾ",
+            category: PhonemeCategory::Rhotic,
+            duration_ms: 30,
+            formants: Some(FormantValues::new(400.0, 1700.0, 2600.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("w", Phoneme {
@@ -414,6 +595,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Approximant,
             duration_ms: 60,
             formants: Some(FormantValues::new(300.0, 700.0, 2400.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("j", Phoneme {
@@ -422,6 +604,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Approximant,
             duration_ms: 60,
             formants: Some(FormantValues::new(280.0, 2300.0, 3000.0)),
+            formants_end: None,
             voiced: true,
         });
 
@@ -432,6 +615,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Silence,
             duration_ms: 100,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
 
@@ -452,6 +636,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(750.0, 1200.0, 2600.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("e", Phoneme {
@@ -460,6 +645,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(450.0, 1900.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("i", Phoneme {
@@ -468,6 +654,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(270.0, 2300.0, 3000.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("o", Phoneme {
@@ -476,6 +663,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(500.0, 900.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("u", Phoneme {
@@ -484,6 +672,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Vowel,
             duration_ms: 100,
             formants: Some(FormantValues::new(300.0, 800.0, 2300.0)),
+            formants_end: None,
             voiced: true,
         });
 
@@ -494,6 +683,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("b", Phoneme {
@@ -502,6 +692,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("t", Phoneme {
@@ -510,6 +701,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("d", Phoneme {
@@ -518,6 +710,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("k", Phoneme {
@@ -526,6 +719,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("g", Phoneme {
@@ -534,6 +728,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Plosive,
             duration_ms: 60,
             formants: None,
+            formants_end: None,
             voiced: true,
         });
 
@@ -544,6 +739,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 80,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("s", Phoneme {
@@ -552,6 +748,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 90,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("x", Phoneme {
@@ -560,6 +757,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 80,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
         phonemes.insert("T", Phoneme {
@@ -568,6 +766,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Fricative,
             duration_ms: 80,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
 
@@ -578,6 +777,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Affricate,
             duration_ms: 100,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
 
@@ -588,6 +788,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Nasal,
             duration_ms: 80,
             formants: Some(FormantValues::new(300.0, 1000.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("n", Phoneme {
@@ -596,6 +797,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Nasal,
             duration_ms: 80,
             formants: Some(FormantValues::new(300.0, 1500.0, 2500.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("J", Phoneme {
@@ -604,6 +806,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Nasal,
             duration_ms: 80,
             formants: Some(FormantValues::new(300.0, 1900.0, 2700.0)),
+            formants_end: None,
             voiced: true,
         });
 
@@ -614,6 +817,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Lateral,
             duration_ms: 70,
             formants: Some(FormantValues::new(350.0, 1100.0, 2700.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("L", Phoneme {
@@ -622,6 +826,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Lateral,
             duration_ms: 80,
             formants: Some(FormantValues::new(300.0, 1900.0, 2700.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("r", Phoneme {
@@ -630,6 +835,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Rhotic,
             duration_ms: 40,
             formants: Some(FormantValues::new(400.0, 1400.0, 2200.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("rr", Phoneme {
@@ -638,6 +844,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Rhotic,
             duration_ms: 120,
             formants: Some(FormantValues::new(400.0, 1400.0, 2200.0)),
+            formants_end: None,
             voiced: true,
         });
 
@@ -648,6 +855,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Approximant,
             duration_ms: 60,
             formants: Some(FormantValues::new(280.0, 2300.0, 3000.0)),
+            formants_end: None,
             voiced: true,
         });
         phonemes.insert("w", Phoneme {
@@ -656,6 +864,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Approximant,
             duration_ms: 60,
             formants: Some(FormantValues::new(300.0, 700.0, 2400.0)),
+            formants_end: None,
             voiced: true,
         });
 
@@ -666,6 +875,7 @@ impl PhonemeInventory {
             category: PhonemeCategory::Silence,
             duration_ms: 100,
             formants: None,
+            formants_end: None,
             voiced: false,
         });
 
@@ -675,16 +885,413 @@ impl PhonemeInventory {
         }
     }
 
+    /// Creates a new phoneme inventory for French.
+    pub fn french() -> Self {
+        let mut phonemes = HashMap::new();
+
+        // French Vowels - Oral
+        phonemes.insert("i", Phoneme {
+            symbol: "i",
+            ipa: "i",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(280.0, 2250.0, 2900.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("e", Phoneme {
+            symbol: "e",
+            ipa: "e",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(370.0, 2100.0, 2700.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("E", Phoneme {
+            symbol: "E",
+            ipa: "ɛ",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(530.0, 1850.0, 2550.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("a", Phoneme {
+            symbol: "a",
+            ipa: "a",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(700.0, 1300.0, 2500.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("O", Phoneme {
+            symbol: "O",
+            ipa: "ɔ",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(500.0, 900.0, 2500.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("o", Phoneme {
+            symbol: "o",
+            ipa: "o",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(380.0, 800.0, 2400.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("u", Phoneme {
+            symbol: "u",
+            ipa: "u",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(300.0, 750.0, 2300.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("y", Phoneme {
+            symbol: "y",
+            ipa: "y",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(280.0, 1800.0, 2200.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("2", Phoneme {
+            symbol: "2",
+            ipa: "ø",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(370.0, 1550.0, 2300.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("9", Phoneme {
+            symbol: "9",
+            ipa: "œ",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(520.0, 1450.0, 2400.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("@", Phoneme {
+            symbol: "@",
+            ipa: "ə",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 100,
+            formants: Some(FormantValues::new(480.0, 1400.0, 2500.0)),
+            formants_end: None,
+            voiced: true,
+        });
+
+        // French Vowels - Nasal (wide F1 bandwidth approximates nasalization)
+        phonemes.insert("A~", Phoneme {
+            symbol: "A~",
+            ipa: "ɑ̃",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 120,
+            formants: Some(FormantValues::with_bandwidths(650.0, 1050.0, 2600.0, 150.0, 110.0, 170.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("E~", Phoneme {
+            symbol: "E~",
+            ipa: "ɛ̃",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 120,
+            formants: Some(FormantValues::with_bandwidths(580.0, 1500.0, 2600.0, 150.0, 110.0, 170.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("O~", Phoneme {
+            symbol: "O~",
+            ipa: "ɔ̃",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 120,
+            formants: Some(FormantValues::with_bandwidths(450.0, 800.0, 2500.0, 150.0, 110.0, 170.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("9~", Phoneme {
+            symbol: "9~",
+            ipa: "œ̃",
+            category: PhonemeCategory::Vowel,
+            duration_ms: 120,
+            formants: Some(FormantValues::with_bandwidths(560.0, 1450.0, 2500.0, 150.0, 110.0, 170.0)),
+            formants_end: None,
+            voiced: true,
+        });
+
+        // French Consonants - Plosives
+        phonemes.insert("p", Phoneme {
+            symbol: "p",
+            ipa: "p",
+            category: PhonemeCategory::Plosive,
+            duration_ms: 60,
+            formants: None,
+            formants_end: None,
+            voiced: false,
+        });
+        phonemes.insert("b", Phoneme {
+            symbol: "b",
+            ipa: "b",
+            category: PhonemeCategory::Plosive,
+            duration_ms: 60,
+            formants: None,
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("t", Phoneme {
+            symbol: "t",
+            ipa: "t",
+            category: PhonemeCategory::Plosive,
+            duration_ms: 60,
+            formants: None,
+            formants_end: None,
+            voiced: false,
+        });
+        phonemes.insert("d", Phoneme {
+            symbol: "d",
+            ipa: "d",
+            category: PhonemeCategory::Plosive,
+            duration_ms: 60,
+            formants: None,
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("k", Phoneme {
+            symbol: "k",
+            ipa: "k",
+            category: PhonemeCategory::Plosive,
+            duration_ms: 60,
+            formants: None,
+            formants_end: None,
+            voiced: false,
+        });
+        phonemes.insert("g", Phoneme {
+            symbol: "g",
+            ipa: "g",
+            category: PhonemeCategory::Plosive,
+            duration_ms: 60,
+            formants: None,
+            formants_end: None,
+            voiced: true,
+        });
+
+        // French Consonants - Fricatives
+        phonemes.insert("f", Phoneme {
+            symbol: "f",
+            ipa: "f",
+            category: PhonemeCategory::Fricative,
+            duration_ms: 80,
+            formants: None,
+            formants_end: None,
+            voiced: false,
+        });
+        phonemes.insert("v", Phoneme {
+            symbol: "v",
+            ipa: "v",
+            category: PhonemeCategory::Fricative,
+            duration_ms: 70,
+            formants: None,
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("s", Phoneme {
+            symbol: "s",
+            ipa: "s",
+            category: PhonemeCategory::Fricative,
+            duration_ms: 90,
+            formants: None,
+            formants_end: None,
+            voiced: false,
+        });
+        phonemes.insert("z", Phoneme {
+            symbol: "z",
+            ipa: "z",
+            category: PhonemeCategory::Fricative,
+            duration_ms: 80,
+            formants: None,
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("S", Phoneme {
+            symbol: "S",
+            ipa: "ʃ",
+            category: PhonemeCategory::Fricative,
+            duration_ms: 90,
+            formants: None,
+            formants_end: None,
+            voiced: false,
+        });
+        phonemes.insert("Z", Phoneme {
+            symbol: "Z",
+            ipa: "ʒ",
+            category: PhonemeCategory::Fricative,
+            duration_ms: 80,
+            formants: None,
+            formants_end: None,
+            voiced: true,
+        });
+
+        // French Consonants - Nasals
+        phonemes.insert("m", Phoneme {
+            symbol: "m",
+            ipa: "m",
+            category: PhonemeCategory::Nasal,
+            duration_ms: 80,
+            formants: Some(FormantValues::new(300.0, 1000.0, 2500.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("n", Phoneme {
+            symbol: "n",
+            ipa: "n",
+            category: PhonemeCategory::Nasal,
+            duration_ms: 80,
+            formants: Some(FormantValues::new(300.0, 1500.0, 2500.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("J", Phoneme {
+            symbol: "J",
+            ipa: "ɲ",
+            category: PhonemeCategory::Nasal,
+            duration_ms: 80,
+            formants: Some(FormantValues::new(300.0, 1900.0, 2700.0)),
+            formants_end: None,
+            voiced: true,
+        });
+
+        // French Consonants - Liquids
+        phonemes.insert("l", Phoneme {
+            symbol: "l",
+            ipa: "l",
+            category: PhonemeCategory::Lateral,
+            duration_ms: 70,
+            formants: Some(FormantValues::new(350.0, 1100.0, 2700.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("R", Phoneme {
+            symbol: "R",
+            ipa: "ʁ",
+            category: PhonemeCategory::Rhotic,
+            duration_ms: 70,
+            formants: Some(FormantValues::new(500.0, 1300.0, 2400.0)),
+            formants_end: None,
+            voiced: true,
+        });
+
+        // French Consonants - Approximants
+        phonemes.insert("j", Phoneme {
+            symbol: "j",
+            ipa: "j",
+            category: PhonemeCategory::Approximant,
+            duration_ms: 60,
+            formants: Some(FormantValues::new(280.0, 2300.0, 3000.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("w", Phoneme {
+            symbol: "w",
+            ipa: "w",
+            category: PhonemeCategory::Approximant,
+            duration_ms: 60,
+            formants: Some(FormantValues::new(300.0, 700.0, 2400.0)),
+            formants_end: None,
+            voiced: true,
+        });
+        phonemes.insert("H", Phoneme {
+            symbol: "H",
+            ipa: "ɥ",
+            category: PhonemeCategory::Approximant,
+            duration_ms: 60,
+            formants: Some(FormantValues::new(280.0, 1800.0, 2300.0)),
+            formants_end: None,
+            voiced: true,
+        });
+
+        // Silence
+        phonemes.insert("_", Phoneme {
+            symbol: "_",
+            ipa: "",
+            category: PhonemeCategory::Silence,
+            duration_ms: 100,
+            formants: None,
+            formants_end: None,
+            voiced: false,
+        });
+
+        Self {
+            phonemes,
+            language: "fr",
+        }
+    }
+
     /// Gets a phoneme by its symbol.
     pub fn get(&self, symbol: &str) -> Option<&Phoneme> {
         self.phonemes.get(symbol)
     }
 
+    /// Looks up a phoneme by its IPA transcription (e.g. `"tʃ"`, `"iː"`).
+    pub fn from_ipa(&self, ipa: &str) -> Option<&Phoneme> {
+        if ipa.is_empty() {
+            return None;
+        }
+        self.phonemes.values().find(|phoneme| phoneme.ipa == ipa)
+    }
+
     /// Returns all phonemes in the inventory.
     pub fn all(&self) -> impl Iterator<Item = &Phoneme> {
         self.phonemes.values()
     }
 
+    /// Computes the spectral envelope of a phoneme's formant filter.
+    ///
+    /// Evaluates the F1-F3 resonator transfer functions (mixed the same way
+    /// as in vowel synthesis) at `points` frequencies evenly spaced from 0 Hz
+    /// to the Nyquist frequency, returning `(frequency_hz, magnitude)` pairs.
+    /// Returns `None` for unknown symbols and phonemes without formants.
+    pub fn spectral_envelope(&self, symbol: &str, points: usize) -> Option<Vec<(f32, f32)>> {
+        let formants = self.get(symbol)?.formants?;
+        let sr = SAMPLE_RATE as f32;
+        let nyquist = sr / 2.0;
+        let resonators = [
+            (formants.f1, formants.b1, 1.0),
+            (formants.f2, formants.b2, 0.5),
+            (formants.f3, formants.b3, 0.25),
+        ];
+
+        let envelope = (0..points)
+            .map(|i| {
+                let freq = i as f32 * nyquist / points.saturating_sub(1).max(1) as f32;
+                let w = 2.0 * PI * freq / sr;
+                let (mut re, mut im) = (0.0, 0.0);
+                for &(f, bw, gain) in &resonators {
+                    // H(z) = a / (1 - b z^-1 - c z^-2), as in the synthesizer's resonator
+                    let c = -f32::exp(-2.0 * PI * bw / sr);
+                    let b = 2.0 * f32::exp(-PI * bw / sr) * f32::cos(2.0 * PI * f / sr);
+                    let a = 1.0 - b - c;
+                    let d_re = 1.0 - b * w.cos() - c * (2.0 * w).cos();
+                    let d_im = b * w.sin() + c * (2.0 * w).sin();
+                    let d_norm = d_re * d_re + d_im * d_im;
+                    re += gain * a * d_re / d_norm;
+                    im -= gain * a * d_im / d_norm;
+                }
+                (freq, (re * re + im * im).sqrt())
+            })
+            .collect();
+
+        Some(envelope)
+    }
+
     /// Returns the number of phonemes in the inventory.
     pub fn len(&self) -> usize {
         self.phonemes.len()
@@ -725,6 +1332,53 @@ mod tests {
         assert!(vowel.voiced);
     }
 
+    #[test]
+    fn test_french_inventory() {
+        let inv = PhonemeInventory::french();
+        assert_eq!(inv.language, "fr");
+
+        let oral = inv.get("O").unwrap().formants.unwrap();
+        let nasal = inv.from_ipa("ɔ̃").unwrap();
+        assert_eq!(nasal.symbol, "O~");
+        let nasal = nasal.formants.unwrap();
+        assert!(nasal.b1 > oral.b1 * 2.0);
+        for ipa in ["ɑ̃", "ɛ̃", "œ̃", "ʁ"] {
+            assert!(inv.from_ipa(ipa).is_some(), "{}", ipa);
+        }
+    }
+
+    #[test]
+    fn test_from_ipa() {
+        let inv = PhonemeInventory::english();
+        assert_eq!(inv.from_ipa("iː").unwrap().symbol, "i");
+        assert_eq!(inv.from_ipa("ɪ").unwrap().symbol, "I");
+        assert_eq!(inv.from_ipa("tʃ").unwrap().symbol, "tS");
+        assert!(inv.from_ipa("").is_none());
+        assert!(inv.from_ipa("x").is_none());
+        assert_eq!(PhonemeInventory::spanish().from_ipa("x").unwrap().symbol, "x");
+    }
+
+    #[test]
+    fn test_visemes() {
+        let english = PhonemeInventory::english();
+        let viseme = |symbol: &str| english.get(symbol).unwrap().viseme();
+        assert_eq!(viseme("p"), Viseme::Closed);
+        assert_eq!(viseme("m"), Viseme::Closed);
+        assert_eq!(viseme("f"), Viseme::LabioDental);
+        assert_eq!(viseme("T"), Viseme::Dental);
+        assert_eq!(viseme("A"), Viseme::Open);
+        assert_eq!(viseme("u"), Viseme::Rounded);
+        assert_eq!(viseme("O"), Viseme::Rounded);
+        assert_eq!(viseme("i"), Viseme::Spread);
+        assert_eq!(viseme("@"), Viseme::Neutral);
+        assert_eq!(viseme("_"), Viseme::Neutral);
+
+        // Front rounded vowels are rounded despite their high F2
+        let french = PhonemeInventory::french();
+        assert_eq!(french.get("y").unwrap().viseme(), Viseme::Rounded);
+        assert_eq!(french.get("i").unwrap().viseme(), Viseme::Spread);
+    }
+
     #[test]
     fn test_formant_values() {
         let formants = FormantValues::new(270.0, 2290.0, 3010.0);
@@ -732,4 +1386,22 @@ mod tests {
         assert_eq!(formants.f2, 2290.0);
         assert_eq!(formants.f3, 3010.0);
     }
+
+    #[test]
+    fn test_spectral_envelope_peaks() {
+        let inv = PhonemeInventory::english();
+        let envelope = inv.spectral_envelope("i", 1024).unwrap();
+        assert_eq!(envelope.len(), 1024);
+
+        let peaks: Vec<f32> = envelope
+            .windows(3)
+            .filter(|w| w[1].1 > w[0].1 && w[1].1 > w[2].1)
+            .map(|w| w[1].0)
+            .collect();
+        assert!(peaks.iter().any(|&f| (f - 270.0).abs() < 50.0));
+        assert!(peaks.iter().any(|&f| (f - 2290.0).abs() < 50.0));
+
+        assert!(inv.spectral_envelope("s", 64).is_none());
+        assert!(inv.spectral_envelope("xyz", 64).is_none());
+    }
 }
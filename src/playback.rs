@@ -0,0 +1,111 @@
+//! Playback through the default audio output device (`playback` feature).
+
+use crate::error::{Result, SynthesizerError};
+use crate::formant::AudioOutput;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Extra time allowed after the last sample is handed to the device, so the
+/// device buffer drains before the stream is dropped.
+const DRAIN_TIME: Duration = Duration::from_millis(200);
+
+impl AudioOutput {
+    /// Plays the audio on the default output device, blocking until it
+    /// has finished.
+    ///
+    /// Audio is resampled to the device's native rate if it differs. Mono
+    /// audio is played on every device channel; otherwise each device
+    /// channel plays the matching channel of the audio, and device channels
+    /// the audio does not have stay silent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if there is no output device, the device cannot
+    /// be opened, or it reports an error during playback.
+    pub fn play(&self) -> Result<()> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| SynthesizerError::AudioError("no default output device".to_string()))?;
+        let supported = device
+            .default_output_config()
+            .map_err(|e| SynthesizerError::AudioError(format!("failed to query output device: {}", e)))?;
+
+        let config: cpal::StreamConfig = supported.config();
+        let audio = self.resample(config.sample_rate.0);
+        let channels = audio.channels.max(1) as usize;
+
+        match supported.sample_format() {
+            SampleFormat::I16 => play_samples::<i16>(&device, &config, audio.samples, channels),
+            SampleFormat::U16 => play_samples::<u16>(&device, &config, audio.samples, channels),
+            SampleFormat::F32 => play_samples::<f32>(&device, &config, audio.samples, channels),
+            format => Err(SynthesizerError::AudioError(format!(
+                "unsupported output sample format: {}",
+                format
+            ))),
+        }
+    }
+}
+
+/// Streams interleaved samples with `source_channels` channels to the
+/// device until they run out, one source frame per device frame.
+fn play_samples<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: Vec<i16>,
+    source_channels: usize,
+) -> Result<()>
+where
+    T: SizedSample + FromSample<i16>,
+{
+    let channels = config.channels as usize;
+    let frames = samples.len() / source_channels;
+    let (done_tx, done_rx) = mpsc::channel();
+    let error_tx = done_tx.clone();
+    let mut position = 0;
+    let mut done = false;
+
+    let stream = device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    let source = if position < frames {
+                        &samples[position * source_channels..(position + 1) * source_channels]
+                    } else {
+                        &[]
+                    };
+                    for (channel, out) in frame.iter_mut().enumerate() {
+                        let sample = if source_channels == 1 {
+                            source.first()
+                        } else {
+                            source.get(channel)
+                        };
+                        *out = T::from_sample(sample.copied().unwrap_or(0));
+                    }
+                    position += 1;
+                }
+                if !done && position >= frames {
+                    done = true;
+                    let _ = done_tx.send(Ok(()));
+                }
+            },
+            move |e| {
+                let _ = error_tx.send(Err(SynthesizerError::AudioError(format!("playback failed: {}", e))));
+            },
+            None,
+        )
+        .map_err(|e| SynthesizerError::AudioError(format!("failed to open output stream: {}", e)))?;
+    stream
+        .play()
+        .map_err(|e| SynthesizerError::AudioError(format!("failed to start playback: {}", e)))?;
+
+    let result = done_rx
+        .recv()
+        .unwrap_or_else(|_| Err(SynthesizerError::AudioError("playback stopped unexpectedly".to_string())));
+    if result.is_ok() {
+        std::thread::sleep(DRAIN_TIME);
+    }
+    result
+}
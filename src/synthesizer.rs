@@ -4,10 +4,40 @@
 //! text-to-phoneme conversion and formant synthesis to generate speech.
 
 use crate::error::{Result, SynthesizerError};
-use crate::formant::{AudioOutput, FormantSynthesizer, SynthesisConfig, SAMPLE_RATE};
-use crate::g2p::{text_to_ipa, G2PConverter};
-use crate::phoneme::PhonemeInventory;
-use crate::voice::{Language, VoiceConfig};
+use crate::formant::{
+    sentence_contour, AudioOutput, Endianness, FormantSynthesizer, PhonemeTiming, SynthesisConfig,
+    MAX_SAMPLE_RATE, MIN_SAMPLE_RATE, SAMPLE_RATE,
+};
+use crate::g2p::{CapsPolicy, ForeignTextPolicy, G2PConverter, IpaOptions, SpanishVariant};
+use crate::phoneme::{
+    Phoneme, PhonemeInventory, StressLevel, Viseme, EMPHASIS_MARK, PARENTHETICAL_MARK, QUESTION_MARK, QUOTE_MARK,
+    STATEMENT_MARK,
+};
+use crate::voice::{EnglishAccent, IntonationProfile, Language, VoiceConfig, VoiceRegistry};
+use std::cell::Cell;
+use std::io::Write;
+use std::time::Duration;
+
+/// Pause between items read by `synthesize_list` (milliseconds).
+const LIST_PAUSE_MS: u32 = 250;
+
+/// Fade applied to the end of audio truncated to fit a duration (milliseconds).
+const FIT_FADE_MS: u32 = 20;
+/// Slowest rate multiplier `FitPolicy::AdjustRate` will use.
+const FIT_MIN_RATE: f32 = 0.25;
+/// Fastest rate multiplier `FitPolicy::AdjustRate` will use.
+const FIT_MAX_RATE: f32 = 4.0;
+
+/// How `Synthesizer::synthesize_to_duration` fits speech into a time slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitPolicy {
+    /// Change the speech rate so the utterance fills the slot.
+    AdjustRate,
+    /// Cut speech that runs long, fading out the end.
+    Truncate,
+    /// Append silence to speech that runs short.
+    Pad,
+}
 
 /// Phoneme output format for TTS model compatibility.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +50,10 @@ pub enum PhonemeFormat {
     Ascii,
 }
 
+/// Mouth shapes over time, as `(viseme, start_sample, end_sample)` entries
+/// in output order.
+pub type VisemeTrack = Vec<(Viseme, usize, usize)>;
+
 
 /// Result of phoneme generation.
 #[derive(Debug, Clone)]
@@ -37,7 +71,11 @@ pub struct PhonemeResult {
 /// The main speech synthesizer.
 ///
 /// This synthesizer uses formant synthesis to generate speech audio
-/// from text, supporting English and Spanish languages.
+/// from text, supporting English, Spanish and French.
+///
+/// `Synthesizer` is `Send + Sync`: synthesis methods take `&self` and
+/// render with an engine of their own, so one instance can be shared
+/// behind an `Arc` and used from many threads at once.
 ///
 /// # Example
 ///
@@ -56,8 +94,10 @@ pub struct Synthesizer {
     config: VoiceConfig,
     g2p_en: G2PConverter,
     g2p_es: G2PConverter,
+    g2p_fr: G2PConverter,
     inventory_en: PhonemeInventory,
     inventory_es: PhonemeInventory,
+    inventory_fr: PhonemeInventory,
 }
 
 impl Synthesizer {
@@ -70,15 +110,31 @@ impl Synthesizer {
 
     /// Creates a new synthesizer with the specified voice configuration.
     pub fn with_config(config: VoiceConfig) -> Result<Self> {
+        let mut g2p_en = G2PConverter::english();
+        g2p_en.set_flapping(config.accent == EnglishAccent::American);
         Ok(Self {
             config,
-            g2p_en: G2PConverter::english(),
+            g2p_en,
             g2p_es: G2PConverter::spanish(),
+            g2p_fr: G2PConverter::french(),
             inventory_en: PhonemeInventory::english(),
             inventory_es: PhonemeInventory::spanish(),
+            inventory_fr: PhonemeInventory::french(),
         })
     }
 
+    /// Creates a new synthesizer with the voice registered under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VoiceError` if the registry has no voice with that name.
+    pub fn with_voice_name(registry: &VoiceRegistry, name: &str) -> Result<Self> {
+        let config = registry
+            .get(name)
+            .ok_or_else(|| SynthesizerError::VoiceError(format!("unknown voice '{}'", name)))?;
+        Self::with_config(config.clone())
+    }
+
     /// Returns the current voice configuration.
     #[must_use]
     pub fn config(&self) -> &VoiceConfig {
@@ -87,6 +143,7 @@ impl Synthesizer {
 
     /// Sets a new voice configuration.
     pub fn set_config(&mut self, config: VoiceConfig) {
+        self.g2p_en.set_flapping(config.accent == EnglishAccent::American);
         self.config = config;
     }
 
@@ -110,36 +167,156 @@ impl Synthesizer {
         self.config.volume = volume.min(200);
     }
 
+    /// Sets the output sample rate in Hz (8000-48000).
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.config.sample_rate = sample_rate.clamp(MIN_SAMPLE_RATE, MAX_SAMPLE_RATE);
+    }
+
+    /// Sets the accent used for English text.
+    pub fn set_accent(&mut self, accent: EnglishAccent) {
+        self.config.accent = accent;
+        self.g2p_en.set_flapping(accent == EnglishAccent::American);
+    }
+
     /// Gets the appropriate G2P converter for the current language.
     fn get_g2p(&self) -> &G2PConverter {
         match self.config.language {
             Language::English => &self.g2p_en,
             Language::Spanish => &self.g2p_es,
+            Language::French => &self.g2p_fr,
         }
     }
 
+    /// Gets the G2P converter for the current language, mutably.
+    fn get_g2p_mut(&mut self) -> &mut G2PConverter {
+        match self.config.language {
+            Language::English => &mut self.g2p_en,
+            Language::Spanish => &mut self.g2p_es,
+            Language::French => &mut self.g2p_fr,
+        }
+    }
+
+    /// Registers an all-caps word for the current language that is spoken
+    /// as a word instead of being spelled out (see
+    /// [`G2PConverter::add_spoken_initialism`]).
+    pub fn add_spoken_initialism(&mut self, word: &str, phonemes: Option<&str>) {
+        self.get_g2p_mut().add_spoken_initialism(word, phonemes);
+    }
+
+    /// Adds a pronunciation to the current language's exception dictionary
+    /// (see [`G2PConverter::add_exception`]).
+    pub fn add_exception(&mut self, word: &str, phonemes: &str) {
+        self.get_g2p_mut().add_exception(word, phonemes);
+    }
+
+    /// Loads a `word<TAB>phonemes` pronunciation dictionary into the current
+    /// language's converter, returning the number of entries loaded (see
+    /// [`G2PConverter::load_exceptions_from_str`]).
+    pub fn load_exceptions_from_str(&mut self, text: &str) -> Result<usize> {
+        self.get_g2p_mut().load_exceptions_from_str(text)
+    }
+
+    /// Sets strict mode on the current language's converter, so text with
+    /// letters it cannot pronounce fails instead of being partly skipped
+    /// (see [`G2PConverter::set_strict`]).
+    pub fn set_strict_g2p(&mut self, strict: bool) {
+        self.get_g2p_mut().set_strict(strict);
+    }
+
+    /// Sets how the current language's converter reads all-caps words
+    /// (see [`G2PConverter::set_caps_policy`]).
+    pub fn set_caps_policy(&mut self, policy: CapsPolicy) {
+        self.get_g2p_mut().set_caps_policy(policy);
+    }
+
+    /// Sets whether the current language's converter marks quoted speech
+    /// to be spoken at a slightly raised pitch (see
+    /// [`G2PConverter::set_quote_detection`]).
+    pub fn set_quote_detection(&mut self, detect: bool) {
+        self.get_g2p_mut().set_quote_detection(detect);
+    }
+
+    /// Sets whether the current language's converter marks parenthetical
+    /// phrases to be spoken lower and faster, between short pauses (see
+    /// [`G2PConverter::set_parenthetical_detection`]).
+    pub fn set_parenthetical_detection(&mut self, detect: bool) {
+        self.get_g2p_mut().set_parenthetical_detection(detect);
+    }
+
+    /// Sets how the current language's converter reads letters it has no
+    /// rules for (see [`ForeignTextPolicy`]).
+    pub fn set_foreign_text_policy(&mut self, policy: ForeignTextPolicy) {
+        self.get_g2p_mut().set_foreign_text_policy(policy);
+    }
+
+    /// Sets whether the current language's converter reduces unstressed
+    /// vowels to schwa (see [`G2PConverter::set_vowel_reduction`]).
+    pub fn set_vowel_reduction(&mut self, reduce: bool) {
+        self.get_g2p_mut().set_vowel_reduction(reduce);
+    }
+
+    /// Sets the regional pronunciation used for Spanish text
+    /// (see [`G2PConverter::set_spanish_variant`]).
+    pub fn set_spanish_variant(&mut self, variant: SpanishVariant) {
+        self.g2p_es.set_spanish_variant(variant);
+    }
+
     /// Gets the appropriate phoneme inventory for the current language.
     fn get_inventory(&self) -> &PhonemeInventory {
         match self.config.language {
             Language::English => &self.inventory_en,
             Language::Spanish => &self.inventory_es,
+            Language::French => &self.inventory_fr,
         }
     }
 
-    /// Creates a formant synthesizer with current configuration.
-    fn create_formant_synthesizer(&self) -> FormantSynthesizer {
-        let synth_config = SynthesisConfig {
-            pitch_hz: self.config.effective_pitch_hz(),
-            rate: self.config.rate_multiplier(),
-            volume: self.config.volume_level().min(1.0),
-            sample_rate: SAMPLE_RATE,
-        };
-        FormantSynthesizer::new(synth_config)
+    /// Creates the formant engine configuration for the current voice.
+    fn synthesis_config(&self) -> SynthesisConfig {
+        SynthesisConfig::from_voice(&self.config)
+    }
+
+    /// Synthesizes speech and plays it on the default output device,
+    /// blocking until playback has finished (`playback` feature).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if the output device cannot be opened or fails
+    /// during playback.
+    #[cfg(feature = "playback")]
+    pub fn play(&self, text: &str) -> Result<()> {
+        self.synthesize(text)?.play()
+    }
+
+    /// Converts text to phonemes and renders them with the given engine configuration.
+    fn render(&self, text: &str, synth_config: SynthesisConfig) -> Result<AudioOutput> {
+        // Convert text to phonemes, with stress marks for the engine
+        let g2p = self.get_g2p();
+        let phonemes = g2p.convert_marked(text)?;
+        self.render_marked(&phonemes, synth_config)
+    }
+
+    /// Renders a marked phoneme sequence with the given engine configuration.
+    fn render_marked(&self, phonemes: &str, synth_config: SynthesisConfig) -> Result<AudioOutput> {
+        let sample_rate = synth_config.sample_rate;
+        if phonemes.is_empty() {
+            return Ok(AudioOutput::new(vec![], sample_rate, 1));
+        }
+
+        // Synthesize phonemes to audio
+        let inventory = self.get_inventory();
+        let mut formant_synth = FormantSynthesizer::new(synth_config);
+        let float_samples = formant_synth.synthesize_phonemes(phonemes, inventory)?;
+        
+        // Convert to PCM16
+        let pcm_samples = formant_synth.to_pcm16(&float_samples);
+
+        Ok(AudioOutput::new(pcm_samples, sample_rate, 1))
     }
 
     /// Synthesizes speech from text and returns the audio data.
     ///
-    /// The returned audio is 16-bit signed PCM at 22050 Hz, mono.
+    /// The returned audio is 16-bit signed PCM at the voice's sample rate
+    /// (22050 Hz by default), mono.
     ///
     /// # Arguments
     ///
@@ -149,23 +326,409 @@ impl Synthesizer {
     ///
     /// Audio data containing the synthesized speech.
     pub fn synthesize(&self, text: &str) -> Result<AudioOutput> {
-        // Convert text to phonemes
+        self.render(text, self.synthesis_config())
+    }
+
+    /// Synthesizes speech and reports where each phoneme lies in the audio.
+    ///
+    /// The audio is the same as [`synthesize`](Self::synthesize) returns.
+    /// Timings are in output order, with an entry for each phoneme and
+    /// each `_` word gap, for lip-sync or word-level subtitles.
+    pub fn synthesize_with_alignment(&self, text: &str) -> Result<(AudioOutput, Vec<PhonemeTiming>)> {
+        let phonemes = self.get_g2p().convert_marked(text)?;
+        let mut formant_synth = FormantSynthesizer::new(self.synthesis_config());
+        let (float_samples, timings) = formant_synth.synthesize_phonemes_aligned(&phonemes, self.get_inventory())?;
+        let pcm_samples = formant_synth.to_pcm16(&float_samples);
+
+        Ok((AudioOutput::new(pcm_samples, self.sample_rate(), 1), timings))
+    }
+
+    /// Synthesizes speech with a track of mouth shapes for facial animation.
+    ///
+    /// Each entry is a viseme with its start and end sample, from
+    /// [`synthesize_with_alignment`](Self::synthesize_with_alignment)'s
+    /// phoneme timings mapped through [`Phoneme::viseme`]; consecutive
+    /// phonemes with the same viseme are merged into one entry.
+    pub fn synthesize_with_visemes(&self, text: &str) -> Result<(AudioOutput, VisemeTrack)> {
+        let (audio, timings) = self.synthesize_with_alignment(text)?;
+        let inventory = self.get_inventory();
+
+        let mut track = VisemeTrack::new();
+        for timing in timings {
+            let symbol = timing.symbol.strip_suffix(':').unwrap_or(&timing.symbol);
+            let viseme = inventory.get(symbol).map_or(Viseme::Neutral, Phoneme::viseme);
+            match track.last_mut() {
+                Some((last, _, end)) if *last == viseme => *end = timing.end_sample,
+                _ => track.push((viseme, timing.start_sample, timing.end_sample)),
+            }
+        }
+        Ok((audio, track))
+    }
+
+    /// Synthesizes speech and writes it to `writer` as raw 16-bit PCM.
+    ///
+    /// Audio is rendered and written one word at a time, so memory use
+    /// stays bounded by the longest word no matter how long the text is.
+    /// The bytes written are the same as `synthesize(text)` serialized with
+    /// [`AudioOutput::to_bytes`].
+    ///
+    /// Returns the number of samples written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AudioError` if writing fails.
+    pub fn synthesize_to_writer<W: Write>(&self, text: &str, endian: Endianness, writer: &mut W) -> Result<usize> {
+        let phonemes = self.get_g2p().convert_marked(text)?;
+        let inventory = self.get_inventory();
+        let mut formant_synth = FormantSynthesizer::new(self.synthesis_config());
+        let mut chunk = Vec::new();
+        let mut written = 0;
+
+        // Word gaps reset the engine's per-word state, so splitting after
+        // each run of them renders exactly what a single pass would, as
+        // long as the sentence contour is worked out over the whole text
+        let symbols: Vec<&str> = phonemes.split_whitespace().collect();
+        let contour = sentence_contour(&symbols, inventory);
+        let mut start = 0;
+        for end in 1..=symbols.len() {
+            let at_word_end = end == symbols.len() || (symbols[end - 1] == "_" && symbols[end] != "_");
+            if !at_word_end {
+                continue;
+            }
+            chunk.clear();
+            formant_synth.synthesize_symbols(
+                &symbols[start..end],
+                |_| None,
+                &contour[start..end],
+                inventory,
+                &mut chunk,
+                None,
+            );
+            start = end;
+            let audio = AudioOutput::new(formant_synth.to_pcm16(&chunk), self.sample_rate(), 1);
+            writer
+                .write_all(&audio.to_bytes(endian))
+                .map_err(|e| SynthesizerError::AudioError(format!("failed to write audio: {}", e)))?;
+            written += audio.samples.len();
+        }
+        writer
+            .flush()
+            .map_err(|e| SynthesizerError::AudioError(format!("failed to write audio: {}", e)))?;
+
+        Ok(written)
+    }
+
+    /// Reads a list of items aloud ("apples, oranges, and bananas").
+    ///
+    /// Non-final items are spoken with the rising contour of a question and
+    /// followed by a pause; the final item is preceded by `conjunction` and
+    /// spoken with the falling contour of a statement.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The list items, in reading order.
+    /// * `conjunction` - The word joining the final item (e.g. "and", "or", "y").
+    pub fn synthesize_list(&self, items: &[&str], conjunction: &str) -> Result<AudioOutput> {
+        let base = self.synthesis_config();
+        let pause_samples = (LIST_PAUSE_MS as f32 / 1000.0 * base.sample_rate as f32 / base.rate) as usize;
         let g2p = self.get_g2p();
-        let phonemes = g2p.convert(text)?;
+        let mut samples = Vec::new();
 
-        if phonemes.is_empty() {
-            return Ok(AudioOutput::new(vec![], SAMPLE_RATE, 1));
+        for (i, item) in items.iter().enumerate() {
+            let is_last = i + 1 == items.len();
+            let text = if is_last && items.len() > 1 {
+                format!("{} {}", conjunction, item)
+            } else {
+                item.to_string()
+            };
+
+            // End the item with the sentence mark for its contour, replacing
+            // any mark its own punctuation produced
+            let phonemes = g2p.convert_marked(&text)?;
+            let mut tokens: Vec<&str> = phonemes.split_whitespace().collect();
+            while tokens.last().is_some_and(|t| *t == QUESTION_MARK || *t == STATEMENT_MARK) {
+                tokens.pop();
+            }
+            if tokens.is_empty() {
+                continue;
+            }
+            tokens.push(if is_last { STATEMENT_MARK } else { QUESTION_MARK });
+            samples.extend(self.render_marked(&tokens.join(" "), base.clone())?.samples);
+
+            if !is_last {
+                samples.extend(std::iter::repeat_n(0, pause_samples));
+            }
         }
 
-        // Synthesize phonemes to audio
+        Ok(AudioOutput::new(samples, self.sample_rate(), 1))
+    }
+
+    /// Speaks the text at normal speed, then again more slowly.
+    ///
+    /// For accessibility: the normal rendering is followed by `gap_ms` of
+    /// silence and a second rendering with the rate divided by
+    /// `slow_factor` (2.0 = half speed).
+    ///
+    /// # Errors
+    ///
+    /// Returns `SynthesisError` if `slow_factor` is not positive.
+    pub fn synthesize_with_repeat(&self, text: &str, slow_factor: f32, gap_ms: u32) -> Result<AudioOutput> {
+        if slow_factor.is_nan() || slow_factor <= 0.0 {
+            return Err(SynthesizerError::SynthesisError(format!(
+                "slow factor must be positive, got {}",
+                slow_factor
+            )));
+        }
+
+        let base = self.synthesis_config();
+        let slow = SynthesisConfig {
+            rate: base.rate / slow_factor,
+            ..base.clone()
+        };
+        let gap_samples = (gap_ms as f32 / 1000.0 * base.sample_rate as f32) as usize;
+
+        let mut samples = self.render(text, base)?.samples;
+        samples.extend(std::iter::repeat_n(0, gap_samples));
+        samples.extend(self.render(text, slow)?.samples);
+
+        Ok(AudioOutput::new(samples, self.sample_rate(), 1))
+    }
+
+    /// Counts the phonemes the text converts to, excluding word gaps.
+    ///
+    /// Runs only grapheme-to-phoneme conversion, so it is a cheap way to
+    /// gauge the complexity of a request before synthesizing it.
+    pub fn phoneme_count(&self, text: &str) -> Result<usize> {
+        let phonemes = self.get_g2p().convert(text)?;
+        Ok(phonemes.split_whitespace().filter(|p| *p != "_").count())
+    }
+
+    /// Estimates the number of samples `synthesize` would produce for the text.
+    ///
+    /// Runs grapheme-to-phoneme conversion and duration math only; no audio
+    /// is generated.
+    pub fn estimate_samples(&self, text: &str) -> Result<usize> {
+        let phonemes = self.get_g2p().convert_marked(text)?;
+        let formant_synth = FormantSynthesizer::new(self.synthesis_config());
+        Ok(formant_synth.estimate_samples(&phonemes, self.get_inventory()))
+    }
+
+    /// Estimates how long the audio `synthesize` would produce for the text
+    /// will last, without generating it (see
+    /// [`estimate_samples`](Self::estimate_samples)).
+    pub fn estimate_duration(&self, text: &str) -> Result<Duration> {
+        let samples = self.estimate_samples(text)?;
+        Ok(Duration::from_secs_f64(samples as f64 / self.sample_rate() as f64))
+    }
+
+    /// Synthesizes speech fitted to a fixed time slot.
+    ///
+    /// With `FitPolicy::AdjustRate` the rate is chosen (within 0.25x to 4x)
+    /// so the speech fills `target`, and any remainder from rounding or
+    /// the rate limits is padded or cut, so the output is exactly `target`
+    /// long. `Truncate` only shortens speech that runs long and `Pad` only
+    /// lengthens speech that runs short; otherwise the audio is unchanged.
+    pub fn synthesize_to_duration(&self, text: &str, target: Duration, policy: FitPolicy) -> Result<AudioOutput> {
+        let base = self.synthesis_config();
+        let target_samples = (target.as_secs_f64() * base.sample_rate as f64).round() as usize;
+
+        let (mut audio, fit_longer, fit_shorter) = match policy {
+            FitPolicy::AdjustRate => {
+                let phonemes = self.get_g2p().convert_marked(text)?;
+                let inventory = self.get_inventory();
+                let estimate = |rate: f32| {
+                    let config = SynthesisConfig { rate, ..base.clone() };
+                    FormantSynthesizer::new(config).estimate_samples(&phonemes, inventory)
+                };
+
+                // Length falls as the rate rises: find the slowest rate that fits
+                let (mut fast, mut slow) = (FIT_MAX_RATE, FIT_MIN_RATE);
+                if estimate(slow) <= target_samples {
+                    fast = slow;
+                } else if estimate(fast) <= target_samples {
+                    for _ in 0..30 {
+                        let mid = (fast + slow) / 2.0;
+                        if estimate(mid) <= target_samples {
+                            fast = mid;
+                        } else {
+                            slow = mid;
+                        }
+                    }
+                }
+
+                let config = SynthesisConfig { rate: fast, ..base };
+                (self.render(text, config)?, true, true)
+            }
+            FitPolicy::Truncate => (self.render(text, base)?, true, false),
+            FitPolicy::Pad => (self.render(text, base)?, false, true),
+        };
+
+        if fit_longer && audio.samples.len() > target_samples {
+            audio.samples.truncate(target_samples);
+            let fade = ((FIT_FADE_MS as f32 / 1000.0 * audio.sample_rate as f32) as usize).min(target_samples);
+            let start = target_samples - fade;
+            for (i, sample) in audio.samples[start..].iter_mut().enumerate() {
+                *sample = (*sample as f32 * (1.0 - (i + 1) as f32 / fade as f32)) as i16;
+            }
+        }
+        if fit_shorter && audio.samples.len() < target_samples {
+            audio.samples.resize(target_samples, 0);
+        }
+
+        Ok(audio)
+    }
+
+    /// Synthesizes a phoneme string directly, bypassing text conversion.
+    ///
+    /// Takes the same whitespace-separated symbols the internal pipeline
+    /// uses: inventory symbols of the current language (with an optional
+    /// trailing `:` for a geminate), `_` for a word gap, and the stress
+    /// marks `'`, `,` and `.`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPhoneme` for symbols not in the current language's
+    /// inventory.
+    pub fn synthesize_phonemes(&self, phonemes: &str) -> Result<AudioOutput> {
+        for symbol in phonemes.split_whitespace() {
+            self.check_phoneme_symbol(symbol)?;
+        }
+
+        let mut formant_synth = FormantSynthesizer::new(self.synthesis_config());
+        let float_samples = formant_synth.synthesize_phonemes(phonemes, self.get_inventory())?;
+        let pcm_samples = formant_synth.to_pcm16(&float_samples);
+
+        Ok(AudioOutput::new(pcm_samples, self.sample_rate(), 1))
+    }
+
+    /// Synthesizes an IPA transcription, such as one produced by
+    /// [`text_to_ipa`](crate::text_to_ipa) or another tool.
+    ///
+    /// The transcription is split into the current language's phonemes by
+    /// longest match, so `iː` is read as one phoneme rather than `i` plus a
+    /// length mark. Whitespace separates words, `ˈ` and `ˌ` mark primary and
+    /// secondary stress, and `.` marks a syllable boundary.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPhoneme` for text that matches no phoneme in the
+    /// current language's inventory.
+    pub fn synthesize_ipa(&self, ipa: &str) -> Result<AudioOutput> {
+        let phonemes = self.ipa_to_phonemes(ipa)?;
+        self.synthesize_phonemes(&phonemes)
+    }
+
+    /// Segments an IPA transcription into inventory symbols.
+    fn ipa_to_phonemes(&self, ipa: &str) -> Result<String> {
         let inventory = self.get_inventory();
-        let mut formant_synth = self.create_formant_synthesizer();
-        let float_samples = formant_synth.synthesize_phonemes(&phonemes, inventory)?;
-        
-        // Convert to PCM16
+        let longest = inventory.all().map(|p| p.ipa.chars().count()).max().unwrap_or(0);
+        let mut symbols = Vec::new();
+
+        for word in ipa.split_whitespace() {
+            if !symbols.is_empty() {
+                symbols.push("_");
+            }
+            let chars: Vec<char> = word.chars().collect();
+            let mut i = 0;
+            while i < chars.len() {
+                let mark = match chars[i] {
+                    'ˈ' => Some(StressLevel::Primary),
+                    'ˌ' => Some(StressLevel::Secondary),
+                    '.' => Some(StressLevel::Unstressed),
+                    _ => None,
+                };
+                if let Some(level) = mark {
+                    symbols.push(level.mark());
+                    i += 1;
+                    continue;
+                }
+
+                let max_len = longest.min(chars.len() - i);
+                let (phoneme, len) = (1..=max_len)
+                    .rev()
+                    .find_map(|len| {
+                        let candidate: String = chars[i..i + len].iter().collect();
+                        inventory.from_ipa(&candidate).map(|phoneme| (phoneme, len))
+                    })
+                    .ok_or_else(|| SynthesizerError::InvalidPhoneme(chars[i..].iter().collect()))?;
+                symbols.push(phoneme.symbol);
+                i += len;
+            }
+        }
+
+        Ok(symbols.join(" "))
+    }
+
+    /// Checks that a symbol is a word gap, a stress, emphasis, quote or
+    /// parenthetical mark, or a (possibly geminate) phoneme of the current
+    /// language.
+    fn check_phoneme_symbol(&self, symbol: &str) -> Result<()> {
+        let base = symbol.strip_suffix(':').unwrap_or(symbol);
+        let known = symbol == "_"
+            || symbol == EMPHASIS_MARK
+            || symbol == QUOTE_MARK
+            || symbol == PARENTHETICAL_MARK
+            || symbol == QUESTION_MARK
+            || symbol == STATEMENT_MARK
+            || StressLevel::from_mark(symbol).is_some()
+            || self.get_inventory().get(base).is_some();
+        if known {
+            Ok(())
+        } else {
+            Err(SynthesizerError::InvalidPhoneme(symbol.to_string()))
+        }
+    }
+
+    /// Synthesizes phonemes given directly, with an optional pitch per phoneme.
+    ///
+    /// Each entry is an inventory symbol (or `_` for a word gap, with an
+    /// optional trailing `:` for a geminate) and a pitch in Hz. Phonemes
+    /// without a pitch use the current voice's pitch, so a melody can be
+    /// pinned on just the phonemes that matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPhoneme` for symbols not in the current language's
+    /// inventory and `SynthesisError` for pitches that are not positive.
+    pub fn synthesize_phonemes_with_pitch(&self, phonemes: &[(String, Option<f32>)]) -> Result<AudioOutput> {
+        let inventory = self.get_inventory();
+        for (symbol, pitch_hz) in phonemes {
+            self.check_phoneme_symbol(symbol)?;
+            if let Some(hz) = pitch_hz.filter(|hz| !(hz.is_finite() && *hz > 0.0)) {
+                return Err(SynthesizerError::SynthesisError(format!(
+                    "pitch for '{}' must be positive, got {} Hz",
+                    symbol, hz
+                )));
+            }
+        }
+
+        let mut formant_synth = FormantSynthesizer::new(self.synthesis_config());
+        let float_samples = formant_synth.synthesize_phonemes_with_pitch(phonemes, inventory)?;
+        let pcm_samples = formant_synth.to_pcm16(&float_samples);
+
+        Ok(AudioOutput::new(pcm_samples, self.sample_rate(), 1))
+    }
+
+    /// Synthesizes a single inventory phoneme in isolation.
+    ///
+    /// Useful for auditioning formant values: the phoneme is rendered at the
+    /// current voice settings for `duration_ms`, with no neighbours.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPhoneme` if `symbol` is not in the current language's
+    /// inventory.
+    pub fn audition_phoneme(&self, symbol: &str, duration_ms: u32) -> Result<AudioOutput> {
+        let phoneme = self
+            .get_inventory()
+            .get(symbol)
+            .ok_or_else(|| SynthesizerError::InvalidPhoneme(symbol.to_string()))?;
+
+        let mut formant_synth = FormantSynthesizer::new(self.synthesis_config());
+        let float_samples = formant_synth.synthesize_phoneme(phoneme, duration_ms);
         let pcm_samples = formant_synth.to_pcm16(&float_samples);
 
-        Ok(AudioOutput::new(pcm_samples, SAMPLE_RATE, 1))
+        Ok(AudioOutput::new(pcm_samples, self.sample_rate(), 1))
     }
 
     /// Converts text to phonemes without synthesizing audio.
@@ -182,14 +745,10 @@ impl Synthesizer {
     ///
     /// A `PhonemeResult` containing the phonemes.
     pub fn text_to_phonemes(&self, text: &str, format: PhonemeFormat) -> Result<PhonemeResult> {
+        let g2p = self.get_g2p();
         let phonemes = match format {
-            PhonemeFormat::Ascii => {
-                let g2p = self.get_g2p();
-                g2p.convert(text)?
-            }
-            PhonemeFormat::Ipa => {
-                text_to_ipa(text, self.config.language.code())?
-            }
+            PhonemeFormat::Ascii => g2p.convert(text)?,
+            PhonemeFormat::Ipa => g2p.to_ipa(text, &IpaOptions::default())?,
         };
 
         Ok(PhonemeResult {
@@ -200,16 +759,115 @@ impl Synthesizer {
         })
     }
 
+    /// Converts many texts to phonemes in one call.
+    ///
+    /// Results are returned in input order. The current language's
+    /// converter is shared by every item; with the `rayon` feature enabled
+    /// the items are converted in parallel.
+    pub fn text_to_phonemes_batch(
+        &self,
+        texts: &[&str],
+        format: PhonemeFormat,
+    ) -> Result<Vec<PhonemeResult>> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            texts
+                .par_iter()
+                .map(|text| self.text_to_phonemes(text, format))
+                .collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            texts
+                .iter()
+                .map(|text| self.text_to_phonemes(text, format))
+                .collect()
+        }
+    }
+
     /// Gets the sample rate used for audio output.
     #[must_use]
     pub fn sample_rate(&self) -> u32 {
-        SAMPLE_RATE
+        self.synthesis_config().sample_rate
     }
 
     /// Gets the supported languages.
     #[must_use]
     pub fn supported_languages() -> &'static [Language] {
-        &[Language::English, Language::Spanish]
+        Language::all()
+    }
+}
+
+/// A reusable synthesis session for interactive, turn-based speech.
+///
+/// Chat and assistant UIs synthesize many short turns with the same voice.
+/// A session keeps the converters, the formant engine and its sample buffer
+/// alive between turns; call [`reset`](Self::reset) to start the next turn
+/// from a clean engine state.
+///
+/// # Example
+///
+/// ```
+/// use parlador::{SynthesisSession, VoiceConfig};
+///
+/// let mut session = SynthesisSession::new(VoiceConfig::default())?;
+/// let greeting = session.speak("Hello")?;
+/// session.reset();
+/// let answer = session.speak("How can I help?")?;
+/// # Ok::<(), parlador::SynthesizerError>(())
+/// ```
+pub struct SynthesisSession {
+    synth: Synthesizer,
+    engine: FormantSynthesizer,
+    buffer: Vec<f32>,
+}
+
+impl SynthesisSession {
+    /// Creates a new session with the given voice configuration.
+    pub fn new(config: VoiceConfig) -> Result<Self> {
+        Ok(Self::from_synthesizer(Synthesizer::with_config(config)?))
+    }
+
+    /// Creates a session that speaks with an existing synthesizer.
+    pub fn from_synthesizer(synth: Synthesizer) -> Self {
+        let engine = FormantSynthesizer::new(synth.synthesis_config());
+        Self {
+            synth,
+            engine,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the current voice configuration.
+    #[must_use]
+    pub fn config(&self) -> &VoiceConfig {
+        self.synth.config()
+    }
+
+    /// Sets a new voice configuration, starting from a fresh engine.
+    pub fn set_config(&mut self, config: VoiceConfig) {
+        self.synth.set_config(config);
+        self.engine = FormantSynthesizer::new(self.synth.synthesis_config());
+    }
+
+    /// Synthesizes one turn of speech.
+    ///
+    /// The engine state carries over from the previous turn unless
+    /// [`reset`](Self::reset) is called in between.
+    pub fn speak(&mut self, text: &str) -> Result<AudioOutput> {
+        let phonemes = self.synth.get_g2p().convert_marked(text)?;
+
+        self.buffer.clear();
+        self.engine
+            .synthesize_phonemes_into(&phonemes, self.synth.get_inventory(), &mut self.buffer)?;
+
+        Ok(AudioOutput::new(self.engine.to_pcm16(&self.buffer), self.synth.sample_rate(), 1))
+    }
+
+    /// Resets the engine state between turns.
+    pub fn reset(&mut self) {
+        self.engine.reset();
     }
 }
 
@@ -243,6 +901,52 @@ pub enum AudioOutputType {
     SynchronousPlayback = 2,
 }
 
+/// Voice parameters set through `espeak_set_parameter` (espeak-ng compatible).
+///
+/// Values use espeak-ng's scales:
+///
+/// | Parameter | Range | Default | Parlador setting |
+/// |-----------|-------|---------|------------------|
+/// | `Rate` | 50-500 | 175 | Words per minute, as is |
+/// | `Volume` | 0-200 | 100 | Volume, as is |
+/// | `Pitch` | 0-100 | 50 | Pitch adjustment `(value - 50) * 2`, so 0 to 100 maps to -100 to 100 |
+/// | `Range` | 0-100 | 50 | Intonation: 0 is monotone, below 40 newsreader, 70 and above expressive |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EspeakParameter {
+    /// Speech rate in words per minute.
+    Rate,
+    /// Volume.
+    Volume,
+    /// Base pitch.
+    Pitch,
+    /// Pitch range (intonation).
+    Range,
+}
+
+impl EspeakParameter {
+    /// Clamps a value to the parameter's range.
+    fn clamp(self, value: i32) -> i32 {
+        match self {
+            EspeakParameter::Rate => value.clamp(50, 500),
+            EspeakParameter::Volume => value.clamp(0, 200),
+            EspeakParameter::Pitch | EspeakParameter::Range => value.clamp(0, 100),
+        }
+    }
+
+    /// Index of the parameter in `ESPEAK_PARAMETERS`.
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Default values of every `EspeakParameter`, by index.
+const ESPEAK_DEFAULTS: [i32; 4] = [175, 100, 50, 50];
+
+thread_local! {
+    /// Current espeak-ng parameter values of the calling thread, by index.
+    static ESPEAK_PARAMETERS: Cell<[i32; 4]> = const { Cell::new(ESPEAK_DEFAULTS) };
+}
+
 /// Initialize the synthesizer (espeak-ng compatible).
 ///
 /// # Arguments
@@ -268,36 +972,188 @@ pub fn espeak_initialize(
 ///
 /// # Arguments
 ///
-/// * `name` - Voice name (language code like "en" or "es").
+/// * `name` - Voice name: a language code like "en" or "es", optionally
+///   with a variant like "en+f3" (see [`VoiceConfig::from_espeak_name`]).
 pub fn espeak_set_voice_by_name(name: &str) -> Result<()> {
-    if Language::from_code(name).is_some() {
-        Ok(())
+    VoiceConfig::from_espeak_name(name).map(|_| ())
+}
+
+/// Set a voice parameter (espeak-ng compatible).
+///
+/// Parameters apply to later `espeak_synth` calls on the same thread, until
+/// changed again or reset by `espeak_terminate`. See [`EspeakParameter`]
+/// for each parameter's range; values outside it are clamped.
+///
+/// # Arguments
+///
+/// * `param` - The parameter to set.
+/// * `value` - The new value, or the change to apply if `relative`.
+/// * `relative` - If true, add `value` to the current value.
+pub fn espeak_set_parameter(param: EspeakParameter, value: i32, relative: bool) -> Result<()> {
+    ESPEAK_PARAMETERS.with(|parameters| {
+        let mut values = parameters.get();
+        let value = if relative { values[param.index()].saturating_add(value) } else { value };
+        values[param.index()] = param.clamp(value);
+        parameters.set(values);
+    });
+    Ok(())
+}
+
+/// Get a voice parameter (espeak-ng compatible).
+///
+/// # Arguments
+///
+/// * `param` - The parameter to get.
+/// * `current` - If true, the current value; otherwise the default.
+pub fn espeak_get_parameter(param: EspeakParameter, current: bool) -> i32 {
+    if current {
+        ESPEAK_PARAMETERS.with(|parameters| parameters.get()[param.index()])
     } else {
-        Err(SynthesizerError::UnsupportedLanguage(name.to_string()))
+        ESPEAK_DEFAULTS[param.index()]
     }
 }
 
+/// Applies the current espeak-ng parameters to a voice configuration.
+fn apply_espeak_parameters(config: VoiceConfig) -> VoiceConfig {
+    let get = |param| espeak_get_parameter(param, true);
+    let intonation = match get(EspeakParameter::Range) {
+        0 => IntonationProfile::Monotone,
+        1..=39 => IntonationProfile::Newsreader,
+        40..=69 => IntonationProfile::Neutral,
+        _ => IntonationProfile::Expressive,
+    };
+    config
+        .with_rate(get(EspeakParameter::Rate) as u32)
+        .with_volume(get(EspeakParameter::Volume) as u8)
+        .with_pitch(((get(EspeakParameter::Pitch) - 50) * 2) as i8)
+        .with_intonation(intonation)
+}
+
 /// Synthesize text to audio (espeak-ng compatible).
 ///
 /// # Arguments
 ///
 /// * `text` - Text to synthesize.
-/// * `language` - Language code.
+/// * `language` - Language code, optionally with a variant like "en+f3".
 ///
 /// # Returns
 ///
-/// Audio samples as i16 PCM.
+/// Audio samples as i16 PCM, spoken with the parameters set by
+/// `espeak_set_parameter`.
 pub fn espeak_synth(text: &str, language: &str) -> Result<Vec<i16>> {
-    let lang = Language::from_code(language)
-        .ok_or_else(|| SynthesizerError::UnsupportedLanguage(language.to_string()))?;
-    
-    let config = VoiceConfig::new(lang);
+    let config = apply_espeak_parameters(VoiceConfig::from_espeak_name(language)?);
     let synth = Synthesizer::with_config(config)?;
     let audio = synth.synthesize(text)?;
     
     Ok(audio.samples)
 }
 
+/// An event reported by `espeak_synth_with_callback` (espeak-ng compatible).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EspeakEvent<'a> {
+    /// A sentence starts; followed by the `WordStart` of its first word.
+    SentenceStart,
+    /// A word starts, at this character (not byte) offset in the text,
+    /// counting from 0. Words expanded from one token, such as the digits
+    /// of a number, all point at the token.
+    WordStart {
+        /// Character offset of the word in the text.
+        char_offset: usize,
+    },
+    /// The next stretch of audio, as i16 PCM.
+    SampleData(&'a [i16]),
+    /// Synthesis has finished.
+    End,
+}
+
+/// Synthesize text, reporting audio and word and sentence positions to a
+/// callback (espeak-ng compatible).
+///
+/// The callback receives the audio as `SampleData` chunks, with each
+/// `SentenceStart` and `WordStart` placed between the chunks where that
+/// sentence or word begins, then a final `End`. As with `espeak_synth`,
+/// nothing is played: the events are simulated from the finished audio,
+/// so they arrive as fast as the callback consumes them rather than in
+/// step with a playback device. A word that makes no sound is reported
+/// where the next sound starts.
+///
+/// # Arguments
+///
+/// * `text` - Text to synthesize.
+/// * `language` - Language code, optionally with a variant like "en+f3".
+/// * `callback` - Called with each event, in order.
+pub fn espeak_synth_with_callback(
+    text: &str,
+    language: &str,
+    mut callback: impl FnMut(EspeakEvent<'_>),
+) -> Result<()> {
+    let config = apply_espeak_parameters(VoiceConfig::from_espeak_name(language)?);
+    let synth = Synthesizer::with_config(config)?;
+    let inventory = synth.get_inventory();
+
+    // Each word of the text as it is spoken, with the token it was read from
+    let (marked, sources) = synth.get_g2p().convert_marked_with_sources(text)?;
+    let words = phoneme_words(&marked);
+    let token_offsets = token_char_offsets(text);
+
+    // First sample of each word that makes a sound
+    let mut formant_synth = FormantSynthesizer::new(synth.synthesis_config());
+    let (float_samples, timings) = formant_synth.synthesize_phonemes_aligned(&marked, inventory)?;
+    let samples = formant_synth.to_pcm16(&float_samples);
+    let mut word_starts = Vec::new();
+    let mut at_word_start = true;
+    for timing in &timings {
+        if timing.symbol == "_" {
+            at_word_start = true;
+        } else if at_word_start {
+            word_starts.push(timing.start_sample);
+            at_word_start = false;
+        }
+    }
+    let mut word_starts = word_starts.into_iter();
+
+    let mut position = 0;
+    for (i, (word, &source)) in words.iter().zip(&sources).enumerate() {
+        let audible = word.iter().any(|t| inventory.get(t.strip_suffix(':').unwrap_or(t)).is_some());
+        let start = if audible { word_starts.next().unwrap_or(samples.len()) } else { position };
+        let start = start.max(position);
+        if start > position {
+            callback(EspeakEvent::SampleData(&samples[position..start]));
+            position = start;
+        }
+        if i == 0 || words[i - 1].iter().any(|t| *t == QUESTION_MARK || *t == STATEMENT_MARK) {
+            callback(EspeakEvent::SentenceStart);
+        }
+        callback(EspeakEvent::WordStart { char_offset: token_offsets[source] });
+    }
+    if position < samples.len() {
+        callback(EspeakEvent::SampleData(&samples[position..]));
+    }
+    callback(EspeakEvent::End);
+
+    Ok(())
+}
+
+/// The character offset where each whitespace-separated token of the text
+/// starts.
+fn token_char_offsets(text: &str) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut in_token = false;
+    for (char_offset, c) in text.chars().enumerate() {
+        if !c.is_whitespace() && !in_token {
+            offsets.push(char_offset);
+        }
+        in_token = !c.is_whitespace();
+    }
+    offsets
+}
+
+/// Splits a phoneme sequence into its words' tokens, skipping empty gaps.
+fn phoneme_words(phonemes: &str) -> Vec<Vec<&str>> {
+    let tokens: Vec<&str> = phonemes.split_whitespace().collect();
+    tokens.split(|t| *t == "_").filter(|word| !word.is_empty()).map(<[&str]>::to_vec).collect()
+}
+
 /// Convert text to phonemes (espeak-ng compatible).
 ///
 /// # Arguments
@@ -327,14 +1183,15 @@ pub fn espeak_text_to_phonemes(text: &str, language: &str, ipa: bool) -> Result<
 }
 
 /// Terminate the synthesizer (espeak-ng compatible).
-/// This is a no-op since we don't have global state.
+/// Resets the calling thread's parameters to their defaults.
 pub fn espeak_terminate() {
-    // No-op - no global state to clean up
+    ESPEAK_PARAMETERS.with(|parameters| parameters.set(ESPEAK_DEFAULTS));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{band_share, f0};
 
     #[test]
     fn test_synthesizer_creation() {
@@ -359,6 +1216,15 @@ mod tests {
         assert!(!audio.unwrap().is_empty());
     }
 
+    #[test]
+    fn test_synthesize_french() {
+        let config = VoiceConfig::new(Language::French);
+        let synth = Synthesizer::with_config(config).unwrap();
+        let audio = synth.synthesize("bonjour le monde");
+        assert!(audio.is_ok());
+        assert!(!audio.unwrap().is_empty());
+    }
+
     #[test]
     fn test_text_to_phonemes_ipa() {
         let synth = Synthesizer::new().unwrap();
@@ -375,6 +1241,479 @@ mod tests {
         assert!(!result.unwrap().phonemes.is_empty());
     }
 
+    #[test]
+    fn test_text_to_phonemes_round_trip() {
+        // Every symbol the G2P emits must be in the inventory ("x" is two)
+        for (language, text) in [(Language::English, "six boxes"), (Language::Spanish, "el taxi")] {
+            let synth = Synthesizer::with_config(VoiceConfig::new(language)).unwrap();
+            let phonemes = synth.text_to_phonemes(text, PhonemeFormat::Ascii).unwrap().phonemes;
+            let audio = synth.synthesize_phonemes(&phonemes).unwrap();
+            assert!(!audio.is_empty(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_text_to_phonemes_batch_matches_single() {
+        let synth = Synthesizer::new().unwrap();
+        let texts = ["hello", "world", "speech synthesis"];
+        for format in [PhonemeFormat::Ipa, PhonemeFormat::Ascii] {
+            let batch = synth.text_to_phonemes_batch(&texts, format).unwrap();
+            assert_eq!(batch.len(), texts.len());
+            for (text, result) in texts.iter().zip(&batch) {
+                let single = synth.text_to_phonemes(text, format).unwrap();
+                assert_eq!(result.text, *text);
+                assert_eq!(result.phonemes, single.phonemes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unstressed_vowel_reduction() {
+        // Energy around the first formant of each vowel in "about"
+        let f1_shares = |reduce: bool| {
+            let mut synth = Synthesizer::new().unwrap();
+            synth.set_vowel_reduction(reduce);
+            let (audio, timings) = synth.synthesize_with_alignment("about").unwrap();
+            let share = |symbol: &str| {
+                let t = timings.iter().find(|t| t.symbol == symbol).unwrap();
+                band_share(&audio.samples[t.start_sample..t.end_sample], 600..800)
+            };
+            (share(if reduce { "@" } else { "&" }), share("aU"))
+        };
+
+        // The unstressed vowel is reduced to a schwa with a lower first
+        // formant than both the stressed vowel and the full vowel
+        let (unstressed, stressed) = f1_shares(true);
+        let (full, _) = f1_shares(false);
+        assert!(unstressed < stressed, "{} vs {}", unstressed, stressed);
+        assert!(unstressed < full, "{} vs {}", unstressed, full);
+    }
+
+    #[test]
+    fn test_audition_phoneme() {
+        let synth = Synthesizer::new().unwrap();
+        let i = synth.audition_phoneme("i", 300).unwrap();
+        let u = synth.audition_phoneme("u", 300).unwrap();
+        assert!(!i.is_empty() && !u.is_empty());
+        // /i/ has a high second formant, /u/ a low one
+        assert!(band_share(&i.samples, 1800..2800) > 2.0 * band_share(&u.samples, 1800..2800));
+
+        let err = synth.audition_phoneme("xyz", 300).unwrap_err();
+        assert!(matches!(err, SynthesizerError::InvalidPhoneme(_)));
+    }
+
+    #[test]
+    fn test_synthesize_with_repeat() {
+        let synth = Synthesizer::new().unwrap();
+        let normal = synth.synthesize("hello").unwrap();
+        let repeat = synth.synthesize_with_repeat("hello", 2.0, 300).unwrap();
+
+        // First pass is the normal rendering, followed by the silent gap
+        let gap = (0.3 * SAMPLE_RATE as f32) as usize;
+        let first_end = normal.samples.len();
+        assert_eq!(&repeat.samples[..first_end], &normal.samples[..]);
+        assert!(repeat.samples[first_end..first_end + gap].iter().all(|&s| s == 0));
+
+        // Second pass is audible and slower than the first
+        let second = &repeat.samples[first_end + gap..];
+        assert!(second.len() > normal.samples.len());
+        assert!(second.iter().any(|&s| s.abs() > 1000));
+
+        assert!(synth.synthesize_with_repeat("hello", 0.0, 300).is_err());
+    }
+
+    #[test]
+    fn test_synthesize_to_duration() {
+        let synth = Synthesizer::new().unwrap();
+        let text = "hello world";
+        let natural = synth.synthesize(text).unwrap().samples.len();
+        let samples_for = |secs: f64| (secs * SAMPLE_RATE as f64).round() as usize;
+        let secs = |samples: usize| Duration::from_secs_f64(samples as f64 / SAMPLE_RATE as f64);
+
+        // Adjusting the rate fills the slot with speech, not silence
+        for target in [natural * 2 / 3, natural * 3 / 2] {
+            let audio = synth.synthesize_to_duration(text, secs(target), FitPolicy::AdjustRate).unwrap();
+            assert_eq!(audio.samples.len(), target);
+            let trailing_silence = audio.samples.iter().rev().take_while(|&&s| s == 0).count();
+            assert!(trailing_silence < samples_for(0.05));
+        }
+
+        // Truncation cuts and fades out
+        let short = secs(natural / 2);
+        let audio = synth.synthesize_to_duration(text, short, FitPolicy::Truncate).unwrap();
+        assert_eq!(audio.samples.len(), natural / 2);
+        assert_eq!(*audio.samples.last().unwrap(), 0);
+        let unchanged = synth.synthesize_to_duration(text, secs(natural * 2), FitPolicy::Truncate).unwrap();
+        assert_eq!(unchanged.samples.len(), natural);
+
+        // Padding appends silence
+        let audio = synth.synthesize_to_duration(text, secs(natural * 2), FitPolicy::Pad).unwrap();
+        assert_eq!(audio.samples.len(), natural * 2);
+        assert!(audio.samples[natural..].iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_synthesize_phonemes_with_pitch() {
+        let synth = Synthesizer::new().unwrap();
+        let phonemes = vec![("A".to_string(), Some(100.0)), ("A".to_string(), Some(200.0))];
+        let audio = synth.synthesize_phonemes_with_pitch(&phonemes).unwrap();
+
+        let half = audio.samples.len() / 2;
+        let first = f0(&audio.samples[..half]);
+        let second = f0(&audio.samples[half..]);
+        assert!((first - 100.0).abs() < 3.0, "first half at {} Hz", first);
+        assert!((second - 200.0).abs() < 6.0, "second half at {} Hz", second);
+
+        let unknown = vec![("xyz".to_string(), None)];
+        assert!(matches!(
+            synth.synthesize_phonemes_with_pitch(&unknown),
+            Err(SynthesizerError::InvalidPhoneme(_))
+        ));
+    }
+
+    #[test]
+    fn test_synthesize_phonemes_bypasses_g2p() {
+        let synth = Synthesizer::new().unwrap();
+        let phonemes = synth.get_g2p().convert_marked("hello world").unwrap();
+        let direct = synth.synthesize_phonemes(&phonemes).unwrap();
+        assert_eq!(direct.samples, synth.synthesize("hello world").unwrap().samples);
+
+        assert!(matches!(
+            synth.synthesize_phonemes("h E l o _ w 3 r l dd"),
+            Err(SynthesizerError::InvalidPhoneme(symbol)) if symbol == "dd"
+        ));
+    }
+
+    #[test]
+    fn test_synthesize_ipa() {
+        let synth = Synthesizer::new().unwrap();
+        assert_eq!(synth.ipa_to_phonemes("ˈtʃiːz ɪt").unwrap(), "' tS i z _ I t");
+        assert_eq!(
+            synth.synthesize_ipa("ˈtʃiːz").unwrap().samples,
+            synth.synthesize_phonemes("' tS i z").unwrap().samples
+        );
+        assert!(matches!(synth.synthesize_ipa("ʔa"), Err(SynthesizerError::InvalidPhoneme(_))));
+    }
+
+    #[test]
+    fn test_strict_g2p_rejects_unknown_letters() {
+        let mut synth = Synthesizer::new().unwrap();
+        assert!(synth.synthesize("hello мир").is_ok());
+        synth.set_strict_g2p(true);
+        assert!(matches!(synth.synthesize("hello мир"), Err(SynthesizerError::PhonemeError(_))));
+    }
+
+    #[test]
+    fn test_caps_shout_is_louder() {
+        let mut synth = Synthesizer::new().unwrap();
+        let rms = |audio: &AudioOutput| {
+            let sum: f64 = audio.samples.iter().map(|&s| (s as f64).powi(2)).sum();
+            (sum / audio.samples.len() as f64).sqrt()
+        };
+
+        synth.set_caps_policy(CapsPolicy::Shout);
+        let shouted = synth.synthesize("STOP").unwrap();
+        let plain = synth.synthesize("stop").unwrap();
+        assert_eq!(shouted.samples.len(), plain.samples.len());
+        assert!(rms(&shouted) > 1.1 * rms(&plain));
+    }
+
+    #[test]
+    fn test_quoted_speech_is_higher() {
+        let mut synth = Synthesizer::new().unwrap();
+        let plain = synth.synthesize("\"ah\"").unwrap();
+        synth.set_quote_detection(true);
+        let quoted = synth.synthesize("\"ah\"").unwrap();
+        assert_eq!(quoted.samples.len(), plain.samples.len());
+        assert!(f0(&quoted.samples) > 1.04 * f0(&plain.samples));
+    }
+
+    #[test]
+    fn test_parenthetical_is_lower_and_faster() {
+        let mut synth = Synthesizer::new().unwrap();
+        let plain = synth.synthesize("(ah)").unwrap();
+        synth.set_parenthetical_detection(true);
+        let aside = synth.synthesize("(ah)").unwrap();
+        assert!(aside.samples.len() < plain.samples.len());
+        assert!(f0(&aside.samples) < 0.95 * f0(&plain.samples));
+
+        // The pauses and the faster rate are estimated exactly
+        let text = "I was, you know, tired (as usual) today.";
+        let audio = synth.synthesize(text).unwrap();
+        assert_eq!(synth.estimate_samples(text).unwrap(), audio.samples.len());
+        synth.set_parenthetical_detection(false);
+        assert!(audio.samples.len() > synth.synthesize(text).unwrap().samples.len());
+    }
+
+    #[test]
+    fn test_synthesize_list_adds_pauses() {
+        let synth = Synthesizer::new().unwrap();
+        let list = synth.synthesize_list(&["apples", "oranges", "bananas"], "and").unwrap();
+        let plain = synth.synthesize("apples oranges and bananas").unwrap();
+        assert!(list.samples.len() > plain.samples.len());
+
+        assert!(synth.synthesize_list(&[], "and").unwrap().is_empty());
+        assert!(!synth.synthesize_list(&["apples"], "and").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_synthesize_list_intonation() {
+        // Non-final items rise like a question, the final item falls like a statement
+        let synth = Synthesizer::new().unwrap();
+        let list = synth.synthesize_list(&["apples", "oranges!"], "and").unwrap();
+        let first = synth.synthesize("apples?").unwrap();
+        let last = synth.synthesize("and oranges.").unwrap();
+        assert_eq!(&list.samples[..first.samples.len()], &first.samples[..]);
+        assert_eq!(&list.samples[list.samples.len() - last.samples.len()..], &last.samples[..]);
+        assert_ne!(first.samples, synth.synthesize("apples.").unwrap().samples);
+    }
+
+    #[test]
+    fn test_phoneme_count_and_estimate() {
+        let synth = Synthesizer::new().unwrap();
+        let short = synth.phoneme_count("hello").unwrap();
+        let long = synth.phoneme_count("hello world, this is longer").unwrap();
+        assert_eq!(short, 4);
+        assert!(long > short);
+
+        let text = "the quick brown fox";
+        let estimate = synth.estimate_samples(text).unwrap();
+        assert_eq!(estimate, synth.synthesize(text).unwrap().samples.len());
+    }
+
+    #[test]
+    fn test_estimate_duration() {
+        let synth = Synthesizer::new().unwrap();
+        let text = "the quick brown fox";
+        let duration = synth.estimate_duration(text).unwrap();
+        let audio = synth.synthesize(text).unwrap();
+        assert!((duration.as_secs_f64() - audio.duration_secs()).abs() < 1e-6);
+        assert_eq!(synth.estimate_duration("").unwrap(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_paragraph_break_pause() {
+        let longest_silence = |audio: &AudioOutput| {
+            audio
+                .samples
+                .split(|&s| s != 0)
+                .map(|run| run.len())
+                .max()
+                .unwrap_or(0)
+        };
+
+        let synth = Synthesizer::new().unwrap();
+        let one_line = synth.synthesize("Hello world. Goodbye now.").unwrap();
+        let paragraphs = synth.synthesize("Hello world.\n\nGoodbye now.").unwrap();
+        assert!(longest_silence(&paragraphs) > longest_silence(&one_line));
+    }
+
+    #[test]
+    fn test_synthesis_config_matches_from_voice() {
+        let config = VoiceConfig::new(Language::Spanish).with_rate(250).with_pitch(-30);
+        let synth = Synthesizer::with_config(config.clone()).unwrap();
+        assert_eq!(synth.synthesis_config(), SynthesisConfig::from_voice(&config));
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Synthesizer>();
+
+        let synth = std::sync::Arc::new(Synthesizer::new().unwrap());
+        let expected = synth.synthesize("hello world").unwrap().samples;
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let synth = std::sync::Arc::clone(&synth);
+                std::thread::spawn(move || synth.synthesize("hello world").unwrap().samples)
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_synthesize_with_alignment() {
+        let synth = Synthesizer::new().unwrap();
+        let (audio, timings) = synth.synthesize_with_alignment("hello world").unwrap();
+        assert_eq!(audio.samples, synth.synthesize("hello world").unwrap().samples);
+
+        let symbols: Vec<&str> = timings.iter().map(|t| t.symbol.as_str()).collect();
+        let phonemes = synth.text_to_phonemes("hello world", PhonemeFormat::Ascii).unwrap().phonemes;
+        assert_eq!(symbols.join(" "), phonemes);
+        assert!(symbols.contains(&"_"));
+
+        // Phonemes follow one another, overlapping only where crossfaded
+        assert_eq!(timings[0].start_sample, 0);
+        assert_eq!(timings.last().unwrap().end_sample, audio.samples.len());
+        for pair in timings.windows(2) {
+            assert!(pair[0].start_sample < pair[1].start_sample);
+            assert!(pair[1].start_sample <= pair[0].end_sample);
+        }
+        let gap = timings.iter().find(|t| t.symbol == "_").unwrap();
+        assert!(audio.samples[gap.start_sample..gap.end_sample].iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_synthesize_with_visemes() {
+        let synth = Synthesizer::new().unwrap();
+        let (audio, track) = synth.synthesize_with_visemes("my boy").unwrap();
+        assert_eq!(track[0].0, Viseme::Closed);
+        assert_eq!(track.first().unwrap().1, 0);
+        assert_eq!(track.last().unwrap().2, audio.samples.len());
+        assert!(track.iter().any(|&(viseme, _, _)| viseme == Viseme::Rounded));
+        // Merged: no two neighbouring entries share a viseme
+        assert!(track.windows(2).all(|pair| pair[0].0 != pair[1].0 && pair[0].2 >= pair[1].1));
+    }
+
+    #[test]
+    fn test_english_accent() {
+        // Length of the medial /t/ (or its flap) in a word
+        let medial = |synth: &Synthesizer, word: &str| {
+            let (_, timings) = synth.synthesize_with_alignment(word).unwrap();
+            let t = timings.iter().rfind(|t| t.symbol == "t" || t.symbol == "4").unwrap();
+            t.end_sample - t.start_sample
+        };
+
+        let mut synth = Synthesizer::new().unwrap();
+        assert!(medial(&synth, "city") * 2 < medial(&synth, "attack"));
+
+        synth.set_accent(EnglishAccent::British);
+        assert_eq!(synth.config().accent, EnglishAccent::British);
+        assert_eq!(medial(&synth, "city"), medial(&synth, "attack"));
+    }
+
+    #[test]
+    fn test_with_voice_name() {
+        let registry = VoiceRegistry::new();
+        let synth = Synthesizer::with_voice_name(&registry, "announcer").unwrap();
+        assert_eq!(synth.config(), registry.get("announcer").unwrap());
+        assert!(matches!(
+            Synthesizer::with_voice_name(&registry, "nobody"),
+            Err(SynthesizerError::VoiceError(_))
+        ));
+    }
+
+    #[test]
+    fn test_noise_seed() {
+        let render = |seed: Option<u32>| {
+            let synth = Synthesizer::with_config(VoiceConfig::default().with_noise_seed(seed)).unwrap();
+            synth.synthesize("she sells sea shells").unwrap().samples
+        };
+
+        // A fixed seed reproduces the audio exactly; different seeds do not
+        assert_eq!(render(Some(7)), render(Some(7)));
+        assert_ne!(render(Some(7)), render(Some(8)));
+        assert_ne!(render(None), render(None));
+    }
+
+    #[test]
+    fn test_configurable_sample_rate() {
+        let default = Synthesizer::new().unwrap().synthesize("hello world").unwrap();
+        assert_eq!(default.sample_rate, SAMPLE_RATE);
+
+        for rate in [8000, 16000, 48000] {
+            let synth = Synthesizer::with_config(VoiceConfig::default().with_sample_rate(rate)).unwrap();
+            assert_eq!(synth.sample_rate(), rate);
+            let audio = synth.synthesize("hello world").unwrap();
+            assert_eq!(audio.sample_rate, rate);
+            // Same speech, so roughly the same duration at any rate
+            assert!((audio.duration_secs() - default.duration_secs()).abs() < 0.01);
+        }
+
+        let mut synth = Synthesizer::new().unwrap();
+        synth.set_sample_rate(4000);
+        assert_eq!(synth.sample_rate(), MIN_SAMPLE_RATE);
+        synth.set_sample_rate(96000);
+        assert_eq!(synth.synthesize("hi").unwrap().sample_rate, MAX_SAMPLE_RATE);
+    }
+
+    #[test]
+    fn test_spoken_initialism_passthrough() {
+        let mut synth = Synthesizer::new().unwrap();
+        let spelled = synth.phoneme_count("HTML").unwrap();
+        synth.add_spoken_initialism("html", Some("h I m @ l"));
+        assert!(synth.phoneme_count("HTML").unwrap() < spelled);
+    }
+
+    #[test]
+    fn test_session_sequential_turns() {
+        let synth = Synthesizer::new().unwrap();
+        let mut session = SynthesisSession::new(VoiceConfig::default()).unwrap();
+
+        let first = session.speak("hello").unwrap();
+        assert_eq!(first.samples, synth.synthesize("hello").unwrap().samples);
+
+        session.reset();
+        let second = session.speak("world").unwrap();
+        assert_eq!(second.samples, synth.synthesize("world").unwrap().samples);
+
+        session.reset();
+        assert_eq!(session.speak("hello").unwrap().samples, first.samples);
+    }
+
+    #[test]
+    fn test_question_rises_statement_falls() {
+        // Median fundamental over voiced 40 ms frames
+        let median_f0 = |samples: &[i16]| {
+            let frame_len = SAMPLE_RATE as usize / 25;
+            let mut pitches: Vec<f32> = samples
+                .chunks_exact(frame_len)
+                .filter(|frame| frame.iter().map(|&s| (s as f32).abs()).sum::<f32>() / frame_len as f32 > 500.0)
+                .map(f0)
+                .collect();
+            pitches.sort_by(f32::total_cmp);
+            pitches[pitches.len() / 2]
+        };
+        let thirds = |text: &str| {
+            let audio = Synthesizer::new().unwrap().synthesize(text).unwrap();
+            let third = audio.samples.len() / 3;
+            (median_f0(&audio.samples[..third]), median_f0(&audio.samples[2 * third..]))
+        };
+
+        let (start, end) = thirds("you are coming home today?");
+        assert!(end > start * 1.05, "question: {} -> {} Hz", start, end);
+        let (start, end) = thirds("you are coming home today.");
+        assert!(end < start, "statement: {} -> {} Hz", start, end);
+    }
+
+    #[test]
+    fn test_synthesize_to_writer_streams_by_word() {
+        /// Records the largest single write.
+        struct ChunkWriter {
+            bytes: Vec<u8>,
+            largest: usize,
+        }
+        impl Write for ChunkWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.largest = self.largest.max(buf.len());
+                self.bytes.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let synth = Synthesizer::new().unwrap();
+        let text = "the quick brown fox jumps over the lazy dog";
+        let mut writer = ChunkWriter { bytes: Vec::new(), largest: 0 };
+        let written = synth.synthesize_to_writer(text, Endianness::Little, &mut writer).unwrap();
+        assert_eq!(writer.bytes, synth.synthesize(text).unwrap().to_bytes(Endianness::Little));
+        assert_eq!(written * 2, writer.bytes.len());
+
+        // Chunk size doesn't grow with the length of the text
+        let long_text = vec![text; 50].join(" ");
+        let mut long_writer = ChunkWriter { bytes: Vec::new(), largest: 0 };
+        synth.synthesize_to_writer(&long_text, Endianness::Little, &mut long_writer).unwrap();
+        assert_eq!(long_writer.largest, writer.largest);
+        assert!(long_writer.largest * 100 < long_writer.bytes.len());
+
+        let sunk = synth.synthesize_to_writer(&long_text, Endianness::Big, &mut std::io::sink()).unwrap();
+        assert_eq!(sunk * 2, long_writer.bytes.len());
+    }
+
     #[test]
     fn test_espeak_compatible_api() {
         let result = espeak_initialize(AudioOutputType::Retrieval, 500, None, 0);
@@ -392,4 +1731,69 @@ mod tests {
 
         espeak_terminate();
     }
+
+    #[test]
+    fn test_espeak_voice_variants() {
+        assert!(espeak_set_voice_by_name("es+m1").is_ok());
+        assert!(espeak_set_voice_by_name("en+x9").is_err());
+
+        let plain = espeak_synth("hello", "en").unwrap();
+        let female = espeak_synth("hello", "en+f3").unwrap();
+        assert_ne!(female, plain);
+    }
+
+    #[test]
+    fn test_espeak_synth_with_callback() {
+        let text = "I have 42 cats. Do you?";
+        let mut samples = Vec::new();
+        let mut markers = Vec::new();
+        espeak_synth_with_callback(text, "en", |event| match event {
+            EspeakEvent::SampleData(data) => samples.extend_from_slice(data),
+            EspeakEvent::WordStart { char_offset } => markers.push(format!("w{}", char_offset)),
+            EspeakEvent::SentenceStart => markers.push("s".to_string()),
+            EspeakEvent::End => markers.push("end".to_string()),
+        })
+        .unwrap();
+
+        // "42" is read as two words, both pointing at the number
+        assert_eq!(markers, ["s", "w0", "w2", "w7", "w7", "w10", "s", "w16", "w19", "end"]);
+        assert_eq!(samples, espeak_synth(text, "en").unwrap());
+    }
+
+    #[test]
+    fn test_espeak_callback_odd_tokens() {
+        // Every text espeak_synth speaks also works with a callback, and
+        // words keep the offset of the token they were read from
+        let text = "x2 cats";
+        let mut offsets = Vec::new();
+        let mut samples = Vec::new();
+        espeak_synth_with_callback(text, "en", |event| match event {
+            EspeakEvent::SampleData(chunk) => samples.extend_from_slice(chunk),
+            EspeakEvent::WordStart { char_offset } => offsets.push(char_offset),
+            _ => {}
+        })
+        .unwrap();
+        assert_eq!(offsets, [0, 3]);
+        assert_eq!(samples, espeak_synth(text, "en").unwrap());
+    }
+
+    #[test]
+    fn test_espeak_parameters() {
+        let natural = espeak_synth("hello world", "en").unwrap();
+
+        espeak_set_parameter(EspeakParameter::Rate, 300, false).unwrap();
+        espeak_set_parameter(EspeakParameter::Rate, 20, true).unwrap();
+        assert_eq!(espeak_get_parameter(EspeakParameter::Rate, true), 320);
+        assert_eq!(espeak_get_parameter(EspeakParameter::Rate, false), 175);
+        assert!(espeak_synth("hello world", "en").unwrap().len() < natural.len() * 3 / 4);
+
+        espeak_set_parameter(EspeakParameter::Pitch, 500, false).unwrap();
+        assert_eq!(espeak_get_parameter(EspeakParameter::Pitch, true), 100);
+        let config = apply_espeak_parameters(VoiceConfig::default());
+        assert_eq!((config.rate, config.pitch), (320, 100));
+
+        espeak_terminate();
+        assert_eq!(espeak_get_parameter(EspeakParameter::Rate, true), 175);
+        assert_eq!(espeak_synth("hello world", "en").unwrap(), natural);
+    }
 }
@@ -0,0 +1,43 @@
+//! Signal analysis helpers shared by the unit tests.
+
+use crate::formant::SAMPLE_RATE;
+use std::f32::consts::PI;
+use std::ops::Range;
+
+/// Power of `samples` at `hz` (a single DFT bin), for a signal sampled at
+/// `sample_rate`.
+pub(crate) fn dft_power<T: Copy + Into<f32>>(samples: &[T], hz: f32, sample_rate: f32) -> f32 {
+    let (mut re, mut im) = (0.0f32, 0.0f32);
+    for (n, &s) in samples.iter().enumerate() {
+        let w = 2.0 * PI * hz * n as f32 / sample_rate;
+        re += s.into() * w.cos();
+        im += s.into() * w.sin();
+    }
+    re * re + im * im
+}
+
+/// Fundamental of a steady stretch of voicing, by autocorrelation over
+/// its middle half.
+pub(crate) fn f0<T: Copy + Into<f32>>(samples: &[T]) -> f32 {
+    let frame: Vec<f32> = samples[samples.len() / 4..samples.len() * 3 / 4].iter().map(|&s| s.into()).collect();
+    let min_lag = (SAMPLE_RATE / 400) as usize;
+    let max_lag = (SAMPLE_RATE / 60) as usize;
+    let best_lag = (min_lag..max_lag)
+        .max_by(|&a, &b| {
+            let corr = |lag: usize| -> f32 { frame.iter().zip(&frame[lag..]).map(|(x, y)| x * y).sum() };
+            corr(a).total_cmp(&corr(b))
+        })
+        .unwrap();
+    SAMPLE_RATE as f32 / best_lag as f32
+}
+
+/// Energy of `samples` between `band_hz`, summed over 10 Hz DFT bins,
+/// as a share of the total energy.
+pub(crate) fn band_share<T: Copy + Into<f32>>(samples: &[T], band_hz: Range<u32>) -> f32 {
+    let total: f32 = samples.iter().map(|&s| s.into() * s.into()).sum();
+    let band: f32 = band_hz
+        .step_by(10)
+        .map(|hz| dft_power(samples, hz as f32, SAMPLE_RATE as f32) / samples.len() as f32)
+        .sum();
+    band / total
+}
@@ -1,25 +1,72 @@
 //! Language and voice configuration for the speech synthesizer.
 
+use crate::error::{Result, SynthesizerError};
+use crate::formant::{MAX_SAMPLE_RATE, MIN_SAMPLE_RATE, NOISE_SEED, SAMPLE_RATE};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
+/// MIDI note number of concert A (A4).
+const MIDI_A4: f32 = 69.0;
+/// Frequency of concert A (A4) in Hz.
+const A4_HZ: f32 = 440.0;
+
+/// Converts a frequency in Hz to a (fractional) MIDI note number.
+///
+/// A4 (440 Hz) is note 69; each semitone is one note.
+#[must_use]
+pub fn hz_to_midi(hz: f32) -> f32 {
+    MIDI_A4 + 12.0 * (hz / A4_HZ).log2()
+}
+
+/// Converts a (fractional) MIDI note number to a frequency in Hz.
+#[must_use]
+pub fn midi_to_hz(note: f32) -> f32 {
+    A4_HZ * semitone_ratio(note - MIDI_A4)
+}
+
+/// Returns the frequency ratio for an interval in semitones.
+///
+/// `+12` doubles the frequency, `-12` halves it.
+#[must_use]
+pub fn semitone_ratio(semitones: f32) -> f32 {
+    2.0_f32.powf(semitones / 12.0)
+}
+
 /// Supported languages for speech synthesis.
+///
+/// With the `serde` feature, serializes as its language code (`"en"`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Language {
     /// English language.
     #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "en"))]
     English,
     /// Spanish language.
+    #[cfg_attr(feature = "serde", serde(rename = "es"))]
     Spanish,
+    /// French language.
+    #[cfg_attr(feature = "serde", serde(rename = "fr"))]
+    French,
 }
 
 impl Language {
+    /// Returns every supported language.
+    #[must_use]
+    pub fn all() -> &'static [Language] {
+        &[Language::English, Language::Spanish, Language::French]
+    }
+
     /// Returns the language code.
     #[must_use]
     pub fn code(&self) -> &'static str {
         match self {
             Language::English => "en",
             Language::Spanish => "es",
+            Language::French => "fr",
         }
     }
 
@@ -29,17 +76,20 @@ impl Language {
         match self {
             Language::English => "English",
             Language::Spanish => "Spanish",
+            Language::French => "French",
         }
     }
 
     /// Creates a Language from a language code string.
     ///
-    /// Accepts common language codes like "en", "eng", "english", "es", "spa", "spanish".
+    /// Accepts common language codes like "en", "eng", "english", "es", "spa",
+    /// "spanish", "fr", "fra", "french".
     #[must_use]
     pub fn from_code(code: &str) -> Option<Self> {
         match code.to_lowercase().as_str() {
             "en" | "eng" | "english" | "en-us" | "en-gb" => Some(Language::English),
             "es" | "spa" | "spanish" | "es-es" | "es-mx" => Some(Language::Spanish),
+            "fr" | "fra" | "fre" | "french" | "fr-fr" | "fr-ca" => Some(Language::French),
             _ => None,
         }
     }
@@ -53,24 +103,37 @@ impl fmt::Display for Language {
 }
 
 /// Voice variant configuration.
+///
+/// With the `serde` feature, serializes as its short code (`"default"`,
+/// `"m1"`-`"m3"`, `"f1"`-`"f3"`, `"child"`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum VoiceVariant {
     /// Default voice.
     #[default]
     Default,
     /// Male voice variant 1.
+    #[cfg_attr(feature = "serde", serde(rename = "m1"))]
     Male1,
     /// Male voice variant 2.
+    #[cfg_attr(feature = "serde", serde(rename = "m2"))]
     Male2,
     /// Male voice variant 3.
+    #[cfg_attr(feature = "serde", serde(rename = "m3"))]
     Male3,
     /// Female voice variant 1.
+    #[cfg_attr(feature = "serde", serde(rename = "f1"))]
     Female1,
     /// Female voice variant 2.
+    #[cfg_attr(feature = "serde", serde(rename = "f2"))]
     Female2,
     /// Female voice variant 3.
+    #[cfg_attr(feature = "serde", serde(rename = "f3"))]
     Female3,
+    /// Child voice.
+    Child,
 }
 
 impl VoiceVariant {
@@ -85,6 +148,36 @@ impl VoiceVariant {
             VoiceVariant::Female1 => 180.0,
             VoiceVariant::Female2 => 200.0,
             VoiceVariant::Female3 => 220.0,
+            VoiceVariant::Child => 260.0,
+        }
+    }
+
+    /// Returns the factor applied to formant frequencies (F1-F3) for this
+    /// variant, modelling its shorter vocal tract (1.0 = adult male).
+    #[must_use]
+    pub fn formant_scale(&self) -> f32 {
+        match self {
+            VoiceVariant::Default | VoiceVariant::Male1 | VoiceVariant::Male2 | VoiceVariant::Male3 => 1.0,
+            VoiceVariant::Female1 | VoiceVariant::Female2 | VoiceVariant::Female3 => 1.15,
+            VoiceVariant::Child => 1.25,
+        }
+    }
+
+    /// Creates a VoiceVariant from an espeak-style variant code.
+    ///
+    /// Accepts "m1" to "m3", "f1" to "f3", "child" and "default".
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_lowercase().as_str() {
+            "default" => Some(VoiceVariant::Default),
+            "m1" => Some(VoiceVariant::Male1),
+            "m2" => Some(VoiceVariant::Male2),
+            "m3" => Some(VoiceVariant::Male3),
+            "f1" => Some(VoiceVariant::Female1),
+            "f2" => Some(VoiceVariant::Female2),
+            "f3" => Some(VoiceVariant::Female3),
+            "child" => Some(VoiceVariant::Child),
+            _ => None,
         }
     }
 
@@ -99,24 +192,115 @@ impl VoiceVariant {
             VoiceVariant::Female1 => "Female 1",
             VoiceVariant::Female2 => "Female 2",
             VoiceVariant::Female3 => "Female 3",
+            VoiceVariant::Child => "Child",
         }
     }
 }
 
+/// A named bundle of prosody settings giving a voice its intonation style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum IntonationProfile {
+    /// The engine's standard stress and emphasis contours.
+    #[default]
+    Neutral,
+    /// Wider pitch movement and stronger emphasis.
+    Expressive,
+    /// Flat pitch with no emphasis boost.
+    Monotone,
+    /// Restrained pitch movement and light emphasis.
+    Newsreader,
+}
+
+impl IntonationProfile {
+    /// Scale applied to pitch movement around the base pitch (1.0 = neutral,
+    /// 0.0 = flat).
+    #[must_use]
+    pub fn pitch_range(&self) -> f32 {
+        match self {
+            IntonationProfile::Neutral => 1.0,
+            IntonationProfile::Expressive => 1.8,
+            IntonationProfile::Monotone => 0.0,
+            IntonationProfile::Newsreader => 0.7,
+        }
+    }
+
+    /// Scale applied to the pitch and volume boost of emphasized words
+    /// (1.0 = neutral, 0.0 = none).
+    #[must_use]
+    pub fn emphasis_scale(&self) -> f32 {
+        match self {
+            IntonationProfile::Neutral => 1.0,
+            IntonationProfile::Expressive => 1.5,
+            IntonationProfile::Monotone => 0.0,
+            IntonationProfile::Newsreader => 0.5,
+        }
+    }
+}
+
+/// Regional accent used for English text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EnglishAccent {
+    /// General American: /t/ and /d/ between vowels become a flap
+    /// ("butter", "city").
+    #[default]
+    American,
+    /// British: /t/ and /d/ keep their full closure.
+    British,
+}
 
 /// Configuration for a voice used in speech synthesis.
+///
+/// With the `serde` feature it can be saved and loaded, e.g. as a TOML
+/// preset. Missing fields take their defaults, and out-of-range values are
+/// clamped as the builder methods would.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct VoiceConfig {
     /// The language for the voice.
     pub language: Language,
     /// The voice variant.
     pub variant: VoiceVariant,
     /// Speech rate (words per minute). Default is 175.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "clamped::rate"))]
     pub rate: u32,
     /// Pitch adjustment (-100 to 100, 0 = default).
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "clamped::pitch"))]
     pub pitch: i8,
     /// Volume (0-200, with 100 being normal). Default is 100.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "clamped::volume"))]
     pub volume: u8,
+    /// Base pitch in Hz, overriding the variant's base pitch when set.
+    pub base_pitch_hz: Option<f32>,
+    /// Intonation style.
+    pub intonation: IntonationProfile,
+    /// Output sample rate in Hz (8000-48000). Default is 22050.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "clamped::sample_rate"))]
+    pub sample_rate: u32,
+    /// Accent used for English text. Default is American.
+    pub accent: EnglishAccent,
+    /// Aspiration noise mixed into voiced sounds (0.0-1.0, 0 = none).
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "clamped::breathiness"))]
+    pub breathiness: f32,
+    /// Voice brightness (-1.0 to 1.0): positive values darken the voice,
+    /// negative values brighten it, 0 leaves it unchanged.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "clamped::spectral_tilt"))]
+    pub spectral_tilt: f32,
+    /// Seed for the synthesis noise; `None` varies it on every call. Default
+    /// is a fixed seed, so the same text and settings give identical audio.
+    /// With the `serde` feature, `None` is written as `"random"`.
+    #[cfg_attr(feature = "serde", serde(with = "noise_seed"))]
+    pub noise_seed: Option<u32>,
+    /// Whisper instead of speaking aloud. Default is false.
+    pub whisper: bool,
+    /// Speak at a constant pitch, overriding intonation. Default is false.
+    pub monotone: bool,
 }
 
 impl VoiceConfig {
@@ -129,6 +313,41 @@ impl VoiceConfig {
             rate: 175,
             pitch: 0,
             volume: 100,
+            base_pitch_hz: None,
+            intonation: IntonationProfile::Neutral,
+            sample_rate: SAMPLE_RATE,
+            accent: EnglishAccent::American,
+            breathiness: 0.0,
+            spectral_tilt: 0.0,
+            noise_seed: Some(NOISE_SEED),
+            whisper: false,
+            monotone: false,
+        }
+    }
+
+    /// Creates a voice configuration from an espeak-style voice name such
+    /// as `"en"` or `"es+f3"`: a language code, optionally followed by `+`
+    /// and a variant code (see [`VoiceVariant::from_code`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedLanguage` for an unknown language code and
+    /// `VoiceError` for an unknown variant.
+    pub fn from_espeak_name(name: &str) -> Result<Self> {
+        let (code, variant) = match name.split_once('+') {
+            Some((code, variant)) => (code, Some(variant)),
+            None => (name, None),
+        };
+        let language =
+            Language::from_code(code).ok_or_else(|| SynthesizerError::UnsupportedLanguage(code.to_string()))?;
+        let config = Self::new(language);
+        match variant {
+            Some(variant) => {
+                let variant = VoiceVariant::from_code(variant)
+                    .ok_or_else(|| SynthesizerError::VoiceError(format!("unknown voice variant '{}'", variant)))?;
+                Ok(config.with_variant(variant))
+            }
+            None => Ok(config),
         }
     }
 
@@ -160,9 +379,74 @@ impl VoiceConfig {
         self
     }
 
+    /// Sets the base pitch from a MIDI note number (69 = A4 = 440 Hz).
+    ///
+    /// Overrides the variant's base pitch; the pitch adjustment still applies.
+    #[must_use]
+    pub fn with_pitch_midi(mut self, note: f32) -> Self {
+        self.base_pitch_hz = Some(midi_to_hz(note));
+        self
+    }
+
+    /// Sets the intonation style.
+    #[must_use]
+    pub fn with_intonation(mut self, profile: IntonationProfile) -> Self {
+        self.intonation = profile;
+        self
+    }
+
+    /// Sets the output sample rate in Hz (8000-48000).
+    #[must_use]
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate.clamp(MIN_SAMPLE_RATE, MAX_SAMPLE_RATE);
+        self
+    }
+
+    /// Sets the accent used for English text.
+    #[must_use]
+    pub fn with_accent(mut self, accent: EnglishAccent) -> Self {
+        self.accent = accent;
+        self
+    }
+
+    /// Sets the breathiness (0.0-1.0).
+    #[must_use]
+    pub fn with_breathiness(mut self, breathiness: f32) -> Self {
+        self.breathiness = breathiness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the spectral tilt (-1.0 to 1.0, positive = darker).
+    #[must_use]
+    pub fn with_spectral_tilt(mut self, tilt: f32) -> Self {
+        self.spectral_tilt = tilt.clamp(-1.0, 1.0);
+        self
+    }
+
+    /// Sets whether to whisper.
+    #[must_use]
+    pub fn with_whisper(mut self, whisper: bool) -> Self {
+        self.whisper = whisper;
+        self
+    }
+
+    /// Sets whether to hold the pitch constant.
+    #[must_use]
+    pub fn with_monotone(mut self, monotone: bool) -> Self {
+        self.monotone = monotone;
+        self
+    }
+
+    /// Sets the noise seed (`None` for different noise on every call).
+    #[must_use]
+    pub fn with_noise_seed(mut self, seed: Option<u32>) -> Self {
+        self.noise_seed = seed;
+        self
+    }
+
     /// Calculates the effective pitch frequency.
     pub fn effective_pitch_hz(&self) -> f32 {
-        let base = self.variant.base_pitch_hz();
+        let base = self.base_pitch_hz.unwrap_or_else(|| self.variant.base_pitch_hz());
         let adjustment = 1.0 + (self.pitch as f32 / 100.0) * 0.5;
         base * adjustment
     }
@@ -178,12 +462,160 @@ impl VoiceConfig {
     }
 }
 
+/// Deserializers clamping `VoiceConfig` fields to the ranges its builder
+/// methods allow.
+#[cfg(feature = "serde")]
+mod clamped {
+    use crate::formant::{MAX_SAMPLE_RATE, MIN_SAMPLE_RATE};
+    use serde::{Deserialize, Deserializer};
+
+    pub fn rate<'de, D: Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+        Ok(u32::deserialize(d)?.clamp(50, 500))
+    }
+
+    pub fn pitch<'de, D: Deserializer<'de>>(d: D) -> Result<i8, D::Error> {
+        Ok(i64::deserialize(d)?.clamp(-100, 100) as i8)
+    }
+
+    pub fn volume<'de, D: Deserializer<'de>>(d: D) -> Result<u8, D::Error> {
+        Ok(u64::deserialize(d)?.min(200) as u8)
+    }
+
+    pub fn sample_rate<'de, D: Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+        Ok(u32::deserialize(d)?.clamp(MIN_SAMPLE_RATE, MAX_SAMPLE_RATE))
+    }
+
+    pub fn breathiness<'de, D: Deserializer<'de>>(d: D) -> Result<f32, D::Error> {
+        Ok(f32::deserialize(d)?.clamp(0.0, 1.0))
+    }
+
+    pub fn spectral_tilt<'de, D: Deserializer<'de>>(d: D) -> Result<f32, D::Error> {
+        Ok(f32::deserialize(d)?.clamp(-1.0, 1.0))
+    }
+}
+
+/// (De)serializes `VoiceConfig::noise_seed` as the seed or `"random"`.
+///
+/// Formats like TOML cannot write a `None`, and a missing field would
+/// default back to the fixed seed.
+#[cfg(feature = "serde")]
+mod noise_seed {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Written in place of `None`.
+    const RANDOM: &str = "random";
+
+    pub fn serialize<S: Serializer>(seed: &Option<u32>, s: S) -> Result<S::Ok, S::Error> {
+        match seed {
+            Some(seed) => s.serialize_u32(*seed),
+            None => s.serialize_str(RANDOM),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u32>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Seed {
+            Fixed(u32),
+            Named(String),
+        }
+
+        match Seed::deserialize(d)? {
+            Seed::Fixed(seed) => Ok(Some(seed)),
+            Seed::Named(name) if name == RANDOM => Ok(None),
+            Seed::Named(name) => Err(D::Error::custom(format!(
+                "invalid noise seed \"{}\", expected a number or \"{}\"",
+                name, RANDOM
+            ))),
+        }
+    }
+}
+
 impl Default for VoiceConfig {
     fn default() -> Self {
         Self::new(Language::English)
     }
 }
 
+/// Voice configurations looked up by name, such as `"narrator"`.
+///
+/// [`new`](Self::new) starts with the built-in presets `narrator`,
+/// `announcer`, `newsreader`, `storyteller` and `robot`. With the `serde`
+/// feature a registry serializes as a table of name to `VoiceConfig`, so a
+/// whole set of presets can be kept in one file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct VoiceRegistry {
+    voices: BTreeMap<String, VoiceConfig>,
+}
+
+impl VoiceRegistry {
+    /// Creates a registry holding the built-in presets.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(
+            "narrator",
+            VoiceConfig::new(Language::English).with_variant(VoiceVariant::Male1).with_rate(160),
+        );
+        registry.register(
+            "announcer",
+            VoiceConfig::new(Language::English)
+                .with_variant(VoiceVariant::Male2)
+                .with_rate(190)
+                .with_volume(130)
+                .with_intonation(IntonationProfile::Expressive),
+        );
+        registry.register(
+            "newsreader",
+            VoiceConfig::new(Language::English)
+                .with_variant(VoiceVariant::Female1)
+                .with_rate(180)
+                .with_intonation(IntonationProfile::Newsreader),
+        );
+        registry.register(
+            "storyteller",
+            VoiceConfig::new(Language::English)
+                .with_variant(VoiceVariant::Female2)
+                .with_rate(150)
+                .with_intonation(IntonationProfile::Expressive)
+                .with_breathiness(0.1),
+        );
+        registry.register("robot", VoiceConfig::new(Language::English).with_monotone(true));
+        registry
+    }
+
+    /// Creates a registry with no voices.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self { voices: BTreeMap::new() }
+    }
+
+    /// Adds a voice under `name`, returning the voice it replaced, if any.
+    pub fn register(&mut self, name: impl Into<String>, config: VoiceConfig) -> Option<VoiceConfig> {
+        self.voices.insert(name.into(), config)
+    }
+
+    /// Returns the voice registered under `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&VoiceConfig> {
+        self.voices.get(name)
+    }
+
+    /// Returns the registered names, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.voices.keys().map(String::as_str)
+    }
+}
+
+impl Default for VoiceRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,7 +632,27 @@ mod tests {
         assert_eq!(Language::from_code("english"), Some(Language::English));
         assert_eq!(Language::from_code("es"), Some(Language::Spanish));
         assert_eq!(Language::from_code("spanish"), Some(Language::Spanish));
-        assert_eq!(Language::from_code("fr"), None);
+        assert_eq!(Language::from_code("fr"), Some(Language::French));
+        assert_eq!(Language::from_code("fra"), Some(Language::French));
+        assert_eq!(Language::from_code("french"), Some(Language::French));
+        assert_eq!(Language::from_code("de"), None);
+    }
+
+    #[test]
+    fn test_language_all() {
+        // Exhaustive match: adding a variant forces this test (and `all`) to be updated
+        let index = |lang: &Language| match lang {
+            Language::English => 0,
+            Language::Spanish => 1,
+            Language::French => 2,
+        };
+
+        let all = Language::all();
+        assert_eq!(all.len(), 3);
+        for (i, lang) in all.iter().enumerate() {
+            assert_eq!(index(lang), i);
+            assert_eq!(Language::from_code(lang.code()), Some(*lang));
+        }
     }
 
     #[test]
@@ -218,6 +670,19 @@ mod tests {
         assert_eq!(config.volume, 150);
     }
 
+    #[test]
+    fn test_breathiness_and_spectral_tilt() {
+        let config = VoiceConfig::new(Language::English)
+            .with_breathiness(2.0)
+            .with_spectral_tilt(-0.5);
+        assert_eq!(config.breathiness, 1.0);
+        assert_eq!(config.spectral_tilt, -0.5);
+
+        let config = config.with_breathiness(-1.0).with_spectral_tilt(3.0);
+        assert_eq!(config.breathiness, 0.0);
+        assert_eq!(config.spectral_tilt, 1.0);
+    }
+
     #[test]
     fn test_effective_pitch() {
         let config = VoiceConfig::new(Language::English)
@@ -229,6 +694,21 @@ mod tests {
         assert!((config.effective_pitch_hz() - expected).abs() < 0.1);
     }
 
+    #[test]
+    fn test_midi_conversion() {
+        assert!((midi_to_hz(69.0) - 440.0).abs() < 1e-3);
+        assert!((hz_to_midi(440.0) - 69.0).abs() < 1e-4);
+        assert!((semitone_ratio(12.0) - 2.0).abs() < 1e-6);
+        assert!((midi_to_hz(81.0) - 880.0).abs() < 1e-2);
+        assert!((hz_to_midi(midi_to_hz(48.5)) - 48.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_with_pitch_midi() {
+        let config = VoiceConfig::new(Language::English).with_pitch_midi(57.0);
+        assert!((config.effective_pitch_hz() - 220.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_rate_clamping() {
         let config = VoiceConfig::new(Language::English).with_rate(1000);
@@ -237,4 +717,72 @@ mod tests {
         let config = VoiceConfig::new(Language::English).with_rate(10);
         assert_eq!(config.rate, 50);
     }
+
+    #[test]
+    fn test_from_espeak_name() {
+        let config = VoiceConfig::from_espeak_name("es+f3").unwrap();
+        assert_eq!(config.language, Language::Spanish);
+        assert_eq!(config.variant, VoiceVariant::Female3);
+        assert_eq!(VoiceConfig::from_espeak_name("en").unwrap(), VoiceConfig::new(Language::English));
+
+        assert!(matches!(
+            VoiceConfig::from_espeak_name("xx+m1"),
+            Err(SynthesizerError::UnsupportedLanguage(_))
+        ));
+        assert!(matches!(VoiceConfig::from_espeak_name("en+m9"), Err(SynthesizerError::VoiceError(_))));
+        assert!(matches!(VoiceConfig::from_espeak_name("en+"), Err(SynthesizerError::VoiceError(_))));
+    }
+
+    #[test]
+    fn test_voice_registry() {
+        let mut registry = VoiceRegistry::new();
+        assert!(registry.names().any(|name| name == "narrator"));
+        assert_eq!(registry.get("narrator").unwrap().variant, VoiceVariant::Male1);
+        assert!(registry.get("nobody").is_none());
+
+        let custom = VoiceConfig::new(Language::Spanish).with_rate(120);
+        assert!(registry.register("lento", custom.clone()).is_none());
+        assert_eq!(registry.get("lento"), Some(&custom));
+        assert_eq!(registry.register("lento", VoiceConfig::default()), Some(custom));
+        assert_eq!(VoiceRegistry::empty().names().count(), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let config = VoiceConfig::new(Language::Spanish)
+            .with_variant(VoiceVariant::Female3)
+            .with_intonation(IntonationProfile::Expressive)
+            .with_rate(220)
+            .with_breathiness(0.25);
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""language":"es""#));
+        assert!(json.contains(r#""variant":"f3""#));
+        assert_eq!(serde_json::from_str::<VoiceConfig>(&json).unwrap(), config);
+
+        let text = toml::to_string(&config).unwrap();
+        assert_eq!(toml::from_str::<VoiceConfig>(&text).unwrap(), config);
+
+        // A random noise seed is written out rather than left to the default
+        let random = config.clone().with_noise_seed(None);
+        let text = toml::to_string(&random).unwrap();
+        assert!(text.contains("noise_seed = \"random\""));
+        assert_eq!(toml::from_str::<VoiceConfig>(&text).unwrap(), random);
+        let json = serde_json::to_string(&random).unwrap();
+        assert_eq!(serde_json::from_str::<VoiceConfig>(&json).unwrap(), random);
+        assert_eq!(toml::from_str::<VoiceConfig>("noise_seed = 7").unwrap().noise_seed, Some(7));
+        assert!(toml::from_str::<VoiceConfig>("noise_seed = \"often\"").is_err());
+
+        // Hand-edited presets: missing fields default, out-of-range values clamp
+        let edited: VoiceConfig = toml::from_str("language = \"fr\"\nrate = 9000\npitch = -300\nvolume = 999").unwrap();
+        assert_eq!(edited.language, Language::French);
+        assert_eq!((edited.rate, edited.pitch, edited.volume), (500, -100, 200));
+        assert_eq!(edited.variant, VoiceVariant::Default);
+        assert!(toml::from_str::<VoiceConfig>("variant = \"m9\"").is_err());
+
+        let registry = VoiceRegistry::new();
+        let text = toml::to_string(&registry).unwrap();
+        assert_eq!(toml::from_str::<VoiceRegistry>(&text).unwrap(), registry);
+    }
 }